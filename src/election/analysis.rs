@@ -0,0 +1,2285 @@
+//! Mod containing functions for analyzing electorates and election outcomes, as opposed to
+//! election methods themselves. These are generally used to benchmark the results of election
+//! methods against some ideal (i.e. the candidate which maximizes social utility).
+
+use crate::election::election_methods::{
+    plurality_tally, CardinalEnum, ElectionMethods, OrdinalEnum,
+};
+use crate::election::election_profile::CandidateID;
+use crate::election::method_registry::{DynMethod, MethodRegistry};
+use crate::election::voters::{
+    ApprovalThresholdBehavior, BetrayingVoter, HonestVoter, RealCardinalVoter, RealOrdinalVoter,
+    StrategicScoreVoter, Voter, Voters,
+};
+use crate::metrics::kendall_tau;
+use crate::utility_functions::*;
+use rand::Rng;
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+const COMPLETE_BALLOT_WARNING: &str = "Every ballot must rank every candidate!";
+
+/// Rank the candidates by their total social utility across all voters, descending, with ties
+/// broken by tie_breaker. This is the "ideal" ranking against which the rankings produced by
+/// election methods can be compared. Requires that the voters contain utility information.
+pub fn utility_ranking<T: Voter, F: Fn(&usize, &usize) -> Ordering + Copy>(
+    voters: &Vec<T>,
+    num_candidates: usize,
+    tie_breaker: F,
+) -> Vec<CandidateID> {
+    let mut totals = vec![0f64; num_candidates];
+    for voter in voters {
+        voter
+            .utilities()
+            .iter()
+            .zip(totals.iter_mut())
+            .for_each(|(&utility, total)| *total += utility);
+    }
+    let mut candidates = generate_candidates(num_candidates);
+    sort_candidates_by_vec(&mut candidates, &totals, tie_breaker);
+    candidates
+}
+
+/// Measure how polarized the electorate is as the spread of its utilities: for each candidate, the
+/// variance across voters of their utility for that candidate, averaged over the candidates. This
+/// is 0 when every voter agrees about every candidate, and at most 0.25 (for utilities in [0, 1]),
+/// reached when the voters are split evenly between utilities of 0 and 1 for every candidate.
+/// Requires that the voters contain utility information.
+pub fn polarization<T: Voter>(voters: &Vec<T>, num_candidates: usize) -> f64 {
+    let num_voters = voters.len() as f64;
+    let total_variance = generate_candidates(num_candidates)
+        .into_iter()
+        .map(|candidate| {
+            let mean = voters
+                .iter()
+                .map(|v| v.candidate_utility(candidate))
+                .sum::<f64>()
+                / num_voters;
+            voters
+                .iter()
+                .map(|v| (v.candidate_utility(candidate) - mean).powi(2))
+                .sum::<f64>()
+                / num_voters
+        })
+        .sum::<f64>();
+    total_variance / num_candidates as f64
+}
+
+/// Compute each voter's regret given the winning candidate: the difference between the utility of
+/// their favorite candidate and the utility of the winner. A voter whose favorite won has regret 0.
+/// Requires that the voters contain utility information.
+pub fn per_voter_regret<T: Voter>(voters: &Vec<T>, winner: CandidateID) -> Vec<f64> {
+    voters
+        .iter()
+        .map(|voter| {
+            let best = voter
+                .utilities()
+                .iter()
+                .copied()
+                .fold(f64::NEG_INFINITY, f64::max);
+            best - voter.candidate_utility(winner)
+        })
+        .collect()
+}
+
+/// Compute the voter satisfaction efficiency (VSE) of the given winner: the total utility of the
+/// winner, less the average total utility of a candidate chosen at random, as a fraction of how
+/// far the best candidate's total utility is above that average. 1.0 means the winner maximizes
+/// social utility and 0.0 means the winner is no better than a random candidate. If every
+/// candidate has the same total utility, the winner is as good as any and 1.0 is returned.
+/// Requires that the voters contain utility information.
+pub fn voter_satisfaction_efficiency<T: Voter>(voters: &Vec<T>, winner: CandidateID) -> f64 {
+    let mut totals = vec![0f64; voters.first().map_or(0, |v| v.utilities().len())];
+    for voter in voters {
+        voter
+            .utilities()
+            .iter()
+            .zip(totals.iter_mut())
+            .for_each(|(&utility, total)| *total += utility);
+    }
+    let average = totals.iter().sum::<f64>() / totals.len() as f64;
+    let best = totals.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+    if best - average <= 0.0 {
+        return 1.0;
+    }
+    (totals[winner.0] - average) / (best - average)
+}
+
+/// Measure how well an elected committee serves the voters: the sum over voters of the utility of
+/// their favorite member of the committee. Requires that the voters contain utility information.
+pub fn committee_utility<T: Voter>(voters: &Vec<T>, winners: &[CandidateID]) -> f64 {
+    voters
+        .iter()
+        .map(|voter| {
+            winners
+                .iter()
+                .map(|&winner| voter.candidate_utility(winner))
+                .fold(0f64, f64::max)
+        })
+        .sum()
+}
+
+/// Measure how proportionally an elected committee serves the voters, in the style of proportional
+/// approval voting: each voter's utilities for the members of the committee are sorted in
+/// descending order and weighted 1, 1/2, 1/3 and so on, and the weighted sums are totalled over
+/// voters. Unlike committee_utility, a voter keeps gaining from further members they like, but by
+/// less each time, so a committee which gives some members to each faction tends to score higher
+/// than one which spends every seat on the majority. Requires that the voters contain utility
+/// information.
+pub fn proportional_committee_utility<T: Voter>(voters: &Vec<T>, winners: &[CandidateID]) -> f64 {
+    voters
+        .iter()
+        .map(|voter| {
+            let mut utilities = winners
+                .iter()
+                .map(|&winner| voter.candidate_utility(winner))
+                .collect::<Vec<_>>();
+            utilities.sort_by(|a, b| b.partial_cmp(a).unwrap());
+            utilities
+                .iter()
+                .enumerate()
+                .map(|(i, utility)| utility / (i + 1) as f64)
+                .sum::<f64>()
+        })
+        .sum()
+}
+
+/// Measure how well the full ranking produced by the given method matches social welfare: the
+/// Kendall tau correlation between the method's ranking and utility_ranking, in [-1, 1], where 1.0
+/// means the method ranks the candidates exactly in order of total utility. Requires that the
+/// voters contain utility information.
+pub fn ranking_quality<T, F, M>(
+    voters: &mut Vec<T>,
+    num_candidates: usize,
+    tie_breaker: F,
+    method: M,
+) -> f64
+where
+    T: Voter,
+    F: Fn(&usize, &usize) -> Ordering + Copy,
+    M: Fn(&mut Vec<T>, usize, F) -> Vec<CandidateID>,
+{
+    let ideal = utility_ranking(voters, num_candidates, tie_breaker);
+    let ranking = method(voters, num_candidates, tie_breaker);
+    kendall_tau(&ranking, &ideal)
+}
+
+/// Run the given method on voters, returning its ranking along with the wall-clock time the method
+/// took, for comparing how methods scale to large electorates.
+pub fn timed_run<T, F, M>(
+    voters: &mut Vec<T>,
+    num_candidates: usize,
+    tie_breaker: F,
+    method: M,
+) -> (Vec<CandidateID>, Duration)
+where
+    T: Voter,
+    F: Fn(&usize, &usize) -> Ordering + Copy,
+    M: Fn(&mut Vec<T>, usize, F) -> Vec<CandidateID>,
+{
+    let start = Instant::now();
+    let ranking = method(voters, num_candidates, tie_breaker);
+    (ranking, start.elapsed())
+}
+
+/// Find how well each candidate finishes across every method in MethodRegistry::new: element i is
+/// (best, worst) for CandidateID(i), where a finish of 1 is a win and num_candidates is last. A
+/// candidate with a wide range is controversial, winning under some methods but losing badly
+/// under others. Every method must be able to run on the voters.
+pub fn finish_range<T, F>(
+    voters: &mut Vec<T>,
+    num_candidates: usize,
+    tie_breaker: F,
+) -> Vec<(usize, usize)>
+where
+    T: Voter + 'static,
+    F: Fn(&usize, &usize) -> Ordering + Copy + 'static,
+{
+    let mut ranges = vec![(usize::MAX, 0); num_candidates];
+    for (_, ranking) in MethodRegistry::new().run_all(voters, num_candidates, tie_breaker) {
+        for (position, &CandidateID(id)) in ranking.iter().enumerate() {
+            let (best, worst) = &mut ranges[id];
+            *best = (*best).min(position + 1);
+            *worst = (*worst).max(position + 1);
+        }
+    }
+    ranges
+}
+
+/// Compare the winners of several methods on the same voters: matrix[i][j] is whether methods[i]
+/// and methods[j] elect different winners. The methods are run one after another on voters, so
+/// voters whose ballots are random (see monte_carlo_winner) should not be used. Boxed methods from
+/// a MethodRegistry can be passed by dereferencing them.
+pub fn disagreement_matrix<T, F>(
+    voters: &mut Vec<T>,
+    num_candidates: usize,
+    tie_breaker: F,
+    methods: &[&DynMethod<T, F>],
+) -> Vec<Vec<bool>>
+where
+    T: Voter,
+    F: Fn(&usize, &usize) -> Ordering + Copy,
+{
+    let winners = methods
+        .iter()
+        .map(|method| method(voters, num_candidates, tie_breaker)[0])
+        .collect::<Vec<_>>();
+    winners
+        .iter()
+        .map(|a| winners.iter().map(|b| a != b).collect())
+        .collect()
+}
+
+/// Check whether the given method violates the participation criterion for new_voter: that is,
+/// whether adding new_voter to the electorate produces a winner that new_voter honestly likes
+/// less than the winner produced when they stay home (the "no-show paradox"). Note that simply
+/// adding a ballot can never hurt the new voter's top choice under IRV, so the criterion is
+/// judged by the new voter's honest preference between the two winners. Requires that new_voter
+/// contains honest preference information.
+pub fn participation_violated<T, F, M>(
+    voters: &Vec<T>,
+    num_candidates: usize,
+    tie_breaker: F,
+    method: M,
+    new_voter: T,
+) -> bool
+where
+    T: Voter + Clone,
+    F: Fn(&usize, &usize) -> Ordering + Copy,
+    M: Fn(&mut Vec<T>, usize, F) -> Vec<CandidateID>,
+{
+    let before = method(&mut voters.clone(), num_candidates, tie_breaker)[0];
+    let mut with_new_voter = voters.clone();
+    with_new_voter.push(new_voter.clone());
+    let after = method(&mut with_new_voter, num_candidates, tie_breaker)[0];
+    new_voter.honest_preference(after, before) == Ordering::Less
+}
+
+/// Check whether the given method violates the later-no-harm criterion for the voter at
+/// voter_index: that is, whether appending the candidate appended to the bottom of their ballot
+/// causes a candidate they already ranked, who won before, to lose. Voters are represented by
+/// their ordinal ballots (cast as for plurality). If appended is already on the voter's ballot,
+/// the ballot is first truncated just above appended, so the comparison is between ranking only
+/// the candidates the voter prefers to appended and also ranking appended below them.
+pub fn later_no_harm_violated<T, F, M>(
+    voters: &Vec<T>,
+    num_candidates: usize,
+    tie_breaker: F,
+    method: M,
+    voter_index: usize,
+    appended: CandidateID,
+) -> bool
+where
+    T: Voter + Clone,
+    F: Fn(&usize, &usize) -> Ordering + Copy,
+    M: Fn(&mut Vec<RealOrdinalVoter>, usize, F) -> Vec<CandidateID>,
+{
+    let mut ballots = voters
+        .clone()
+        .iter_mut()
+        .map(|voter| voter.cast_ordinal_ballot(OrdinalEnum::plurality).clone())
+        .collect::<Vec<_>>();
+    let ballot = &mut ballots[voter_index];
+    if let Some(position) = ballot.iter().position(|&candidate| candidate == appended) {
+        ballot.truncate(position);
+    }
+    let truncated = ballot.clone();
+    let mut electorate = ballots
+        .into_iter()
+        .map(RealOrdinalVoter::new)
+        .collect::<Vec<_>>();
+
+    let before = method(&mut electorate.clone(), num_candidates, tie_breaker)[0];
+    electorate[voter_index].append_preference(appended);
+    let after = method(&mut electorate, num_candidates, tie_breaker)[0];
+    truncated.contains(&before) && after != before
+}
+
+/// Count how many voters have an incentive to compromise under IRV: that is, how many could get
+/// an outcome they honestly prefer to the sincere IRV winner by insincerely ranking some other
+/// candidate they prefer to that winner first (i.e. a more viable frontrunner, to avoid the
+/// center squeeze). Each voter is considered on their own, with every other voter's ordinal
+/// ballot held fixed. Requires that the voters contain honest preference information.
+pub fn irv_compromise_incentive<T, F>(
+    voters: &Vec<T>,
+    num_candidates: usize,
+    tie_breaker: F,
+) -> usize
+where
+    T: Voter + Clone,
+    F: Fn(&usize, &usize) -> Ordering + Copy,
+{
+    let ballots = voters
+        .clone()
+        .iter_mut()
+        .map(|v| RealOrdinalVoter::new(v.cast_ordinal_ballot(OrdinalEnum::irv).clone()))
+        .collect::<Vec<_>>();
+    let sincere = ElectionMethods::irv(&mut ballots.clone(), num_candidates, tie_breaker)[0];
+
+    voters
+        .iter()
+        .enumerate()
+        .filter(|&(i, voter)| {
+            let ballot = ballots[i].ballot();
+            ballot
+                .iter()
+                .skip(1)
+                .filter(|&&compromise| {
+                    voter.honest_preference(compromise, sincere) == Ordering::Greater
+                })
+                .any(|&compromise| {
+                    // Move the compromise candidate to the top of this voter's ballot
+                    let mut insincere = vec![compromise];
+                    insincere.extend(ballot.iter().copied().filter(|&c| c != compromise));
+                    let mut perturbed = ballots.clone();
+                    perturbed[i] = RealOrdinalVoter::new(insincere);
+                    let winner =
+                        ElectionMethods::irv(&mut perturbed, num_candidates, tie_breaker)[0];
+                    voter.honest_preference(winner, sincere) == Ordering::Greater
+                })
+        })
+        .count()
+}
+
+/// Find the smallest number of voters who, by changing their ordinal ballots, can make target win
+/// the given ordinal method, or None if even the whole electorate can't. Voters are represented by
+/// their sincere ordinal ballots (cast as for plurality). The search is exhaustive under one
+/// simplification: every member of a coalition casts the same ballot, ranking target first and
+/// the other candidates in any order. Voters with the same sincere ballot are interchangeable, so
+/// coalitions are enumerated by how many voters they take with each distinct sincere ballot. Even
+/// so, the search grows exponentially in the number of candidates and distinct ballots, so it is
+/// only practical for small elections.
+pub fn min_manipulating_coalition<T, F, M>(
+    voters: &Vec<T>,
+    num_candidates: usize,
+    tie_breaker: F,
+    method: M,
+    target: CandidateID,
+) -> Option<usize>
+where
+    T: Voter + Clone,
+    F: Fn(&usize, &usize) -> Ordering + Copy,
+    M: Fn(&mut Vec<RealOrdinalVoter>, usize, F) -> Vec<CandidateID>,
+{
+    // Group the voters by their sincere ballot
+    let mut groups: Vec<(Vec<CandidateID>, usize)> = Vec::new();
+    for voter in voters.clone().iter_mut() {
+        let ballot = voter.cast_ordinal_ballot(OrdinalEnum::plurality);
+        match groups.iter_mut().find(|(b, _)| b == ballot) {
+            Some((_, count)) => *count += 1,
+            None => groups.push((ballot.clone(), 1)),
+        }
+    }
+
+    // Every ballot ranking target first
+    let mut insincere_ballots = vec![vec![target]];
+    for candidate in generate_candidates(num_candidates) {
+        if candidate == target {
+            continue;
+        }
+        insincere_ballots = insincere_ballots
+            .into_iter()
+            .flat_map(|ballot| {
+                (1..=ballot.len()).map(move |i| {
+                    let mut extended = ballot.clone();
+                    extended.insert(i, candidate);
+                    extended
+                })
+            })
+            .collect();
+    }
+
+    // Whether target wins when coalition[g] voters from groups[g] cast the insincere ballot
+    let target_wins = |coalition: &[usize], insincere: &Vec<CandidateID>| {
+        let mut electorate = Vec::with_capacity(voters.len());
+        for ((sincere, count), &manipulators) in groups.iter().zip(coalition) {
+            (0..manipulators)
+                .for_each(|_| electorate.push(RealOrdinalVoter::new(insincere.clone())));
+            (manipulators..*count)
+                .for_each(|_| electorate.push(RealOrdinalVoter::new(sincere.clone())));
+        }
+        method(&mut electorate, num_candidates, tie_breaker)[0] == target
+    };
+
+    // Try every way of drawing size voters from the groups, starting with groups[g]
+    fn search<W: Fn(&[usize]) -> bool>(
+        groups: &[(Vec<CandidateID>, usize)],
+        coalition: &mut Vec<usize>,
+        size: usize,
+        works: &W,
+    ) -> bool {
+        let g = coalition.len();
+        if g == groups.len() {
+            return size == 0 && works(coalition);
+        }
+        (0..=size.min(groups[g].1)).any(|manipulators| {
+            coalition.push(manipulators);
+            let found = search(groups, coalition, size - manipulators, works);
+            coalition.pop();
+            found
+        })
+    }
+
+    (0..=voters.len()).find(|&size| {
+        insincere_ballots.iter().any(|insincere| {
+            search(&groups, &mut Vec::new(), size, &|coalition: &[usize]| {
+                target_wins(coalition, insincere)
+            })
+        })
+    })
+}
+
+/// Count how many voters could get an outcome they honestly prefer to the sincere winner of the
+/// given method by betraying their favorite candidate, i.e. by ranking them last and withdrawing
+/// their approval (see BetrayingVoter). Each voter is considered on their own, with every other
+/// voter voting sincerely. Methods satisfying the favorite betrayal criterion, such as approval,
+/// should generally produce a count of 0. Requires that the voters contain utility information.
+pub fn favorite_betrayal_incentive<T, F, M>(
+    voters: &Vec<T>,
+    num_candidates: usize,
+    tie_breaker: F,
+    method: M,
+) -> usize
+where
+    T: Voter + Clone,
+    F: Fn(&usize, &usize) -> Ordering + Copy,
+    M: Fn(&mut Vec<BetrayingVoter<T>>, usize, F) -> Vec<CandidateID>,
+{
+    let electorate = |betrayer: Option<usize>| {
+        voters
+            .iter()
+            .enumerate()
+            .map(|(i, voter)| BetrayingVoter::new(voter.clone(), betrayer == Some(i)))
+            .collect::<Vec<_>>()
+    };
+    let sincere = method(&mut electorate(None), num_candidates, tie_breaker)[0];
+    (0..voters.len())
+        .filter(|&i| {
+            let winner = method(&mut electorate(Some(i)), num_candidates, tie_breaker)[0];
+            voters[i].honest_preference(winner, sincere) == Ordering::Greater
+        })
+        .count()
+}
+
+/// Measure how much the voter at voter_index gains by exaggerating: the utility they ascribe to
+/// the winner when they alone min-max the top two finishers of the sincere election, less the
+/// utility they ascribe to the sincere winner. This is negative if exaggerating backfires. Voters
+/// are represented by their sincere cardinal ballots of the given range (cast as for score
+/// voting), so method should be a cardinal method with that range. The exaggerated ballot is the
+/// voter's sincere ballot with the frontrunner they prefer scored range and the other scored 0, as
+/// a StrategicScoreVoter would cast it. With fewer than two candidates there are no frontrunners,
+/// so the benefit is 0. Requires that the voters contain utility information.
+pub fn exaggeration_benefit<T, F, M>(
+    voters: &Vec<T>,
+    num_candidates: usize,
+    voter_index: usize,
+    range: usize,
+    tie_breaker: F,
+    method: M,
+) -> f64
+where
+    T: Voter + Clone,
+    F: Fn(&usize, &usize) -> Ordering + Copy,
+    M: Fn(&mut Vec<RealCardinalVoter>, usize, F) -> Vec<CandidateID>,
+{
+    let ballots = voters
+        .clone()
+        .iter_mut()
+        .map(|v| v.cast_cardinal_ballot(range, CardinalEnum::score_100).clone())
+        .collect::<Vec<_>>();
+    let electorate = |ballots: Vec<Vec<usize>>| {
+        ballots
+            .into_iter()
+            .map(|ballot| RealCardinalVoter::new(range, ballot, tie_breaker))
+            .collect::<Vec<_>>()
+    };
+    let voter = &voters[voter_index];
+    let (sincere, winner) = sincere_and_strategic_winners(
+        &mut electorate(ballots.clone()),
+        num_candidates,
+        tie_breaker,
+        &method,
+        |(first, second)| {
+            let mut ballots = ballots;
+            let exaggerated = &mut ballots[voter_index];
+            match voter.honest_preference(first, second) {
+                Ordering::Greater => (exaggerated[first.0], exaggerated[second.0]) = (range, 0),
+                Ordering::Less => (exaggerated[first.0], exaggerated[second.0]) = (0, range),
+                Ordering::Equal => {}
+            }
+            electorate(ballots)
+        },
+    );
+    voter.candidate_utility(winner) - voter.candidate_utility(sincere)
+}
+
+/// Estimate how robust the given method's winner is by bootstrapping: resample the electorate with
+/// replacement resamples times, re-running the method on each resampled electorate. Returns the
+/// fraction of resamples each candidate won (every candidate is present, possibly with 0).
+pub fn bootstrap_winner_stability<T, F, R, M>(
+    rng: &mut R,
+    voters: &Vec<T>,
+    num_candidates: usize,
+    tie_breaker: F,
+    method: M,
+    resamples: usize,
+) -> HashMap<CandidateID, f64>
+where
+    T: Voter + Clone,
+    F: Fn(&usize, &usize) -> Ordering + Copy,
+    R: Rng,
+    M: Fn(&mut Vec<T>, usize, F) -> Vec<CandidateID>,
+{
+    let mut wins = generate_candidates(num_candidates)
+        .into_iter()
+        .map(|candidate| (candidate, 0usize))
+        .collect::<HashMap<_, _>>();
+    for _ in 0..resamples {
+        let mut resampled = (0..voters.len())
+            .map(|_| voters[rng.gen_range(0..voters.len())].clone())
+            .collect::<Vec<_>>();
+        *wins
+            .entry(method(&mut resampled, num_candidates, tie_breaker)[0])
+            .or_insert(0) += 1;
+    }
+    wins.into_iter()
+        .map(|(candidate, count)| (candidate, count as f64 / resamples as f64))
+        .collect()
+}
+
+/// Estimate how likely each candidate is to win the given method when the voters' ballots are
+/// random (i.e. NoisyVoters): samples times, every voter redraws their ballots and the method is
+/// re-run. Returns the fraction of samples each candidate won (every candidate is present,
+/// possibly with 0). Voters whose ballots are fixed produce the same winner every time.
+pub fn monte_carlo_winner<T, F, M>(
+    voters: &mut Vec<T>,
+    num_candidates: usize,
+    tie_breaker: F,
+    method: M,
+    samples: usize,
+) -> HashMap<CandidateID, f64>
+where
+    T: Voter,
+    F: Fn(&usize, &usize) -> Ordering + Copy,
+    M: Fn(&mut Vec<T>, usize, F) -> Vec<CandidateID>,
+{
+    let mut wins = generate_candidates(num_candidates)
+        .into_iter()
+        .map(|candidate| (candidate, 0usize))
+        .collect::<HashMap<_, _>>();
+    for _ in 0..samples {
+        voters.iter_mut().for_each(|voter| voter.redraw_ballots());
+        *wins
+            .entry(method(voters, num_candidates, tie_breaker)[0])
+            .or_insert(0) += 1;
+    }
+    wins.into_iter()
+        .map(|(candidate, count)| (candidate, count as f64 / samples as f64))
+        .collect()
+}
+
+/// Build an electorate from the given utilities in which the first
+/// round(strategic_fraction * utilities.len()) voters are StrategicScoreVoters min-maxing the given
+/// frontrunners and the rest are HonestVoters. All voters use unscaled utilities and the Mean
+/// approval threshold.
+pub fn mixed_electorate(
+    utilities: &[Vec<f64>],
+    strategic_fraction: f64,
+    frontrunners: (CandidateID, CandidateID),
+) -> Vec<Voters> {
+    let num_strategic = (strategic_fraction * utilities.len() as f64).round() as usize;
+    utilities
+        .iter()
+        .enumerate()
+        .map(|(i, utilities)| {
+            if i < num_strategic {
+                Voters::from(StrategicScoreVoter::new(
+                    utilities.clone(),
+                    false,
+                    ApprovalThresholdBehavior::Mean,
+                    frontrunners,
+                ))
+            } else {
+                Voters::from(HonestVoter::new(
+                    utilities.clone(),
+                    false,
+                    ApprovalThresholdBehavior::Mean,
+                ))
+            }
+        })
+        .collect()
+}
+
+/// Run the given method on an electorate of HonestVoters built from a utility matrix, where
+/// utilities\[v] is the utility vec of voter v, and every voter shares the given scaling and
+/// approval threshold behavior. Returns the method's ranking.
+pub fn run_on_utilities<F, M>(
+    utilities: &Vec<Vec<f64>>,
+    num_candidates: usize,
+    scales: bool,
+    threshold: ApprovalThresholdBehavior,
+    tie_breaker: F,
+    method: M,
+) -> Vec<CandidateID>
+where
+    F: Fn(&usize, &usize) -> Ordering + Copy,
+    M: Fn(&mut Vec<HonestVoter>, usize, F) -> Vec<CandidateID>,
+{
+    let mut voters = utilities
+        .iter()
+        .map(|utilities| HonestVoter::new(utilities.clone(), scales, threshold.clone()))
+        .collect::<Vec<_>>();
+    method(&mut voters, num_candidates, tie_breaker)
+}
+
+/// The setup of a strategy experiment: how many elections to run, how large each one is, and the
+/// fraction of voters who vote strategically in the strategic run of each election
+#[derive(Debug, Copy, Clone)]
+pub struct StrategyTrials {
+    pub trials: usize,
+    pub num_voters: usize,
+    pub num_candidates: usize,
+    pub strategic_fraction: f64,
+}
+
+/// Run the given method on the sincere electorate, then again on the electorate strategize builds
+/// given the sincere election's top two finishers (the frontrunners). Returns the sincere winner
+/// and the strategic winner. With fewer than two candidates there is no one to strategize
+/// against, so the sincere winner is returned for both without a second run.
+fn sincere_and_strategic_winners<T, F, M, S>(
+    sincere: &mut Vec<T>,
+    num_candidates: usize,
+    tie_breaker: F,
+    method: &M,
+    strategize: S,
+) -> (CandidateID, CandidateID)
+where
+    T: Voter,
+    F: Fn(&usize, &usize) -> Ordering + Copy,
+    M: Fn(&mut Vec<T>, usize, F) -> Vec<CandidateID>,
+    S: FnOnce((CandidateID, CandidateID)) -> Vec<T>,
+{
+    let ranking = method(sincere, num_candidates, tie_breaker);
+    if ranking.len() < 2 {
+        return (ranking[0], ranking[0]);
+    }
+    let mut strategic = strategize((ranking[0], ranking[1]));
+    let winner = method(&mut strategic, num_candidates, tie_breaker)[0];
+    (ranking[0], winner)
+}
+
+/// Run each of setup.trials elections of strategy_impact or strategic_winner_change_rate: draw
+/// utilities for setup.num_voters voters from gen_utilities, and pass the sincere and strategic
+/// winners (see sincere_and_strategic_winners) to record along with the all-honest electorate.
+/// In the strategic electorate, setup.strategic_fraction of the voters min-max the frontrunners.
+fn run_strategy_trials<R, F, M, G, C>(
+    rng: &mut R,
+    setup: StrategyTrials,
+    mut gen_utilities: G,
+    tie_breaker: F,
+    method: M,
+    mut record: C,
+) where
+    R: Rng,
+    F: Fn(&usize, &usize) -> Ordering + Copy,
+    M: Fn(&mut Vec<Voters>, usize, F) -> Vec<CandidateID>,
+    G: FnMut(&mut R, usize) -> Vec<f64>,
+    C: FnMut(&Vec<Voters>, CandidateID, CandidateID),
+{
+    for _ in 0..setup.trials {
+        let utilities = (0..setup.num_voters)
+            .map(|_| gen_utilities(rng, setup.num_candidates))
+            .collect::<Vec<_>>();
+        let mut honest = mixed_electorate(&utilities, 0.0, (CandidateID(0), CandidateID(0)));
+        let (sincere, strategic) = sincere_and_strategic_winners(
+            &mut honest,
+            setup.num_candidates,
+            tie_breaker,
+            &method,
+            |frontrunners| mixed_electorate(&utilities, setup.strategic_fraction, frontrunners),
+        );
+        record(&honest, sincere, strategic);
+    }
+}
+
+/// Measure how much strategy degrades the given method. Each of setup.trials trials draws
+/// setup.num_voters utility vecs from gen_utilities and runs the method twice on them: once with
+/// an all-honest electorate, and once with a mixed_electorate in which setup.strategic_fraction of
+/// the voters min-max the honest election's top two finishers. Returns the mean
+/// voter_satisfaction_efficiency of the winner across trials as (honest, strategic). Since
+/// StrategicScoreVoters only strategize on cardinal ballots, the two values only differ for
+/// cardinal methods.
+pub fn strategy_impact<R, F, M, G>(
+    rng: &mut R,
+    setup: StrategyTrials,
+    gen_utilities: G,
+    tie_breaker: F,
+    method: M,
+) -> (f64, f64)
+where
+    R: Rng,
+    F: Fn(&usize, &usize) -> Ordering + Copy,
+    M: Fn(&mut Vec<Voters>, usize, F) -> Vec<CandidateID>,
+    G: FnMut(&mut R, usize) -> Vec<f64>,
+{
+    let (mut honest_total, mut strategic_total) = (0f64, 0f64);
+    run_strategy_trials(
+        rng,
+        setup,
+        gen_utilities,
+        tie_breaker,
+        method,
+        |voters, sincere, strategic| {
+            honest_total += voter_satisfaction_efficiency(voters, sincere);
+            strategic_total += voter_satisfaction_efficiency(voters, strategic);
+        },
+    );
+    (
+        honest_total / setup.trials as f64,
+        strategic_total / setup.trials as f64,
+    )
+}
+
+/// Measure how often strategy changes the winner under the given method. Each of setup.trials
+/// trials draws setup.num_voters utility vecs from gen_utilities and runs the method on an
+/// all-honest electorate and on a mixed_electorate in which setup.strategic_fraction of the voters
+/// min-max the honest election's top two finishers, as in strategy_impact. Returns the fraction of
+/// trials in which the two winners differ, which is 0 if there are fewer than two candidates to
+/// strategize over. Since StrategicScoreVoters only strategize on cardinal ballots, this is also
+/// always 0 for ordinal methods.
+pub fn strategic_winner_change_rate<R, F, M, G>(
+    rng: &mut R,
+    setup: StrategyTrials,
+    gen_utilities: G,
+    tie_breaker: F,
+    method: M,
+) -> f64
+where
+    R: Rng,
+    F: Fn(&usize, &usize) -> Ordering + Copy,
+    M: Fn(&mut Vec<Voters>, usize, F) -> Vec<CandidateID>,
+    G: FnMut(&mut R, usize) -> Vec<f64>,
+{
+    if setup.num_candidates < 2 {
+        return 0f64;
+    }
+    let mut changed = 0usize;
+    run_strategy_trials(
+        rng,
+        setup,
+        gen_utilities,
+        tie_breaker,
+        method,
+        |_, sincere, strategic| {
+            if sincere != strategic {
+                changed += 1;
+            }
+        },
+    );
+    changed as f64 / setup.trials as f64
+}
+
+/// Measure how often the given method elects the Condorcet winner. Each trial draws num_voters
+/// utility vecs from gen_utilities for an electorate of honest voters (unscaled, with the Mean
+/// approval threshold); among the trials which have a Condorcet winner, returns the fraction in
+/// which the method elects them. Returns NaN if no trial had a Condorcet winner.
+pub fn condorcet_efficiency<R, F, M, G>(
+    rng: &mut R,
+    trials: usize,
+    num_voters: usize,
+    num_candidates: usize,
+    mut gen_utilities: G,
+    tie_breaker: F,
+    method: M,
+) -> f64
+where
+    R: Rng,
+    F: Fn(&usize, &usize) -> Ordering + Copy,
+    M: Fn(&mut Vec<HonestVoter>, usize, F) -> Vec<CandidateID>,
+    G: FnMut(&mut R, usize) -> Vec<f64>,
+{
+    let (mut with_winner, mut agreed) = (0usize, 0usize);
+    for _ in 0..trials {
+        let mut voters = (0..num_voters)
+            .map(|_| {
+                HonestVoter::new(
+                    gen_utilities(rng, num_candidates),
+                    false,
+                    ApprovalThresholdBehavior::Mean,
+                )
+            })
+            .collect::<Vec<_>>();
+        let matrix = pairwise_matrix(&mut voters, num_candidates, OrdinalEnum::plurality);
+        if let Some(condorcet_winner) = condorcet_winner(&matrix) {
+            with_winner += 1;
+            if method(&mut voters, num_candidates, tie_breaker)[0] == condorcet_winner {
+                agreed += 1;
+            }
+        }
+    }
+    agreed as f64 / with_winner as f64
+}
+
+/// Measure how often Borda agrees with Condorcet: the condorcet_efficiency of Borda.
+pub fn borda_condorcet_agreement<R, F, G>(
+    rng: &mut R,
+    trials: usize,
+    num_voters: usize,
+    num_candidates: usize,
+    gen_utilities: G,
+    tie_breaker: F,
+) -> f64
+where
+    R: Rng,
+    F: Fn(&usize, &usize) -> Ordering + Copy,
+    G: FnMut(&mut R, usize) -> Vec<f64>,
+{
+    condorcet_efficiency(
+        rng,
+        trials,
+        num_voters,
+        num_candidates,
+        gen_utilities,
+        tie_breaker,
+        ElectionMethods::borda,
+    )
+}
+
+/// Tally how large the Smith set is across simulated elections. Each trial draws num_voters
+/// utility vecs from gen_utilities for an electorate of honest voters (unscaled, with the Mean
+/// approval threshold) and finds its smith_set; the returned map takes each Smith set size to the
+/// number of trials in which it occurred.
+pub fn smith_set_size_distribution<R, G>(
+    rng: &mut R,
+    trials: usize,
+    num_voters: usize,
+    num_candidates: usize,
+    mut gen_utilities: G,
+) -> HashMap<usize, usize>
+where
+    R: Rng,
+    G: FnMut(&mut R, usize) -> Vec<f64>,
+{
+    let mut sizes = HashMap::new();
+    for _ in 0..trials {
+        let mut voters = (0..num_voters)
+            .map(|_| {
+                HonestVoter::new(
+                    gen_utilities(rng, num_candidates),
+                    false,
+                    ApprovalThresholdBehavior::Mean,
+                )
+            })
+            .collect::<Vec<_>>();
+        let matrix = pairwise_matrix(&mut voters, num_candidates, OrdinalEnum::plurality);
+        *sizes.entry(smith_set(&matrix).len()).or_insert(0) += 1;
+    }
+    sizes
+}
+
+/// Measure how the choice of approval threshold affects approval voting. Each trial draws
+/// num_voters utility vecs from gen_utilities, then for each of the thresholds elects an approval
+/// winner from honest voters (unscaled) with that ApprovalThresholdBehavior::Preset threshold.
+/// Returns the mean voter_satisfaction_efficiency of the winner across trials for each threshold,
+/// in the order given.
+pub fn approval_threshold_sweep<R, F, G>(
+    rng: &mut R,
+    trials: usize,
+    num_voters: usize,
+    num_candidates: usize,
+    mut gen_utilities: G,
+    thresholds: &[f64],
+    tie_breaker: F,
+) -> Vec<f64>
+where
+    R: Rng,
+    F: Fn(&usize, &usize) -> Ordering + Copy,
+    G: FnMut(&mut R, usize) -> Vec<f64>,
+{
+    let mut totals = vec![0f64; thresholds.len()];
+    for _ in 0..trials {
+        let utilities = (0..num_voters)
+            .map(|_| gen_utilities(rng, num_candidates))
+            .collect::<Vec<_>>();
+        for (&threshold, total) in thresholds.iter().zip(totals.iter_mut()) {
+            let mut voters = utilities
+                .iter()
+                .map(|utilities| {
+                    HonestVoter::new(
+                        utilities.clone(),
+                        false,
+                        ApprovalThresholdBehavior::Preset(threshold),
+                    )
+                })
+                .collect::<Vec<_>>();
+            let winner = ElectionMethods::approval(&mut voters, num_candidates, tie_breaker)[0];
+            *total += voter_satisfaction_efficiency(&voters, winner);
+        }
+    }
+    totals.iter().map(|total| total / trials as f64).collect()
+}
+
+/// Find a (roughly) strategic equilibrium of an approval election with perfect information. Each
+/// voter starts from their sincere approval ballot; then, in turn, each voter switches to whichever
+/// approval ballot elects the candidate they get the most utility from given everyone else's
+/// current ballot, keeping their ballot unless some other one is strictly better. This repeats
+/// until a full pass changes no ballots or max_iters passes have been made, and the approval
+/// ranking from the final ballots is returned. Every possible ballot is tried for every voter, so
+/// this is only practical for small numbers of candidates. Requires that the voters contain
+/// utility information.
+pub fn approval_best_response_iteration<T, F>(
+    voters: &mut Vec<T>,
+    num_candidates: usize,
+    tie_breaker: F,
+    max_iters: usize,
+) -> Vec<CandidateID>
+where
+    T: Voter,
+    F: Fn(&usize, &usize) -> Ordering + Copy,
+{
+    let mut ballots = voters
+        .iter_mut()
+        .map(|v| {
+            let mut approved = vec![false; num_candidates];
+            v.cast_approval_ballot(CardinalEnum::approval)
+                .iter()
+                .for_each(|&CandidateID(id)| approved[id] = true);
+            approved
+        })
+        .collect::<Vec<_>>();
+    let mut totals = vec![0usize; num_candidates];
+    for ballot in &ballots {
+        (0..num_candidates)
+            .filter(|&id| ballot[id])
+            .for_each(|id| totals[id] += 1);
+    }
+
+    let winner = |totals: &Vec<usize>| {
+        (0..num_candidates)
+            .max_by(|&a, &b| totals[a].cmp(&totals[b]).then(tie_breaker(&a, &b)))
+            .unwrap()
+    };
+
+    for _ in 0..max_iters {
+        let mut changed = false;
+        for (voter, ballot) in voters.iter().zip(ballots.iter_mut()) {
+            // Tally everyone else's ballots
+            let mut others = totals.clone();
+            (0..num_candidates)
+                .filter(|&id| ballot[id])
+                .for_each(|id| others[id] -= 1);
+
+            let outcome = |candidate_ballot: &Vec<bool>| {
+                let mut with_ballot = others.clone();
+                (0..num_candidates)
+                    .filter(|&id| candidate_ballot[id])
+                    .for_each(|id| with_ballot[id] += 1);
+                voter.candidate_utility(CandidateID(winner(&with_ballot)))
+            };
+
+            // Try every non-empty ballot
+            let mut best = (outcome(ballot), ballot.clone());
+            for mask in 1usize..(1 << num_candidates) {
+                let candidate_ballot = (0..num_candidates)
+                    .map(|id| mask & (1 << id) != 0)
+                    .collect::<Vec<_>>();
+                let utility = outcome(&candidate_ballot);
+                if utility > best.0 {
+                    best = (utility, candidate_ballot);
+                }
+            }
+
+            if best.1 != *ballot {
+                *ballot = best.1;
+                totals = others;
+                (0..num_candidates)
+                    .filter(|&id| ballot[id])
+                    .for_each(|id| totals[id] += 1);
+                changed = true;
+            }
+        }
+        if !changed {
+            break;
+        }
+    }
+
+    let mut candidates = generate_candidates(num_candidates);
+    sort_candidates_by_vec(&mut candidates, &totals, tie_breaker);
+    candidates
+}
+
+/// Compress an electorate by grouping voters who cast identical ordinal ballots (as for
+/// plurality). Returns one (weight, voter) pair per distinct ballot, in order of first appearance,
+/// where voter is the first voter to cast that ballot and weight is the number of voters who did.
+/// Voters in a group may differ in their utilities or other ballots, so the compressed form is only
+/// faithful for methods which use ordinal ballots alone.
+pub fn compress_electorate<T: Voter + Clone>(voters: &mut Vec<T>) -> Vec<(usize, T)> {
+    let mut groups: Vec<(usize, T)> = Vec::new();
+    let mut index_of: HashMap<Vec<CandidateID>, usize> = HashMap::new();
+    for voter in voters.iter_mut() {
+        let ballot = voter.cast_ordinal_ballot(OrdinalEnum::plurality).clone();
+        match index_of.get(&ballot) {
+            Some(&index) => groups[index].0 += 1,
+            None => {
+                index_of.insert(ballot, groups.len());
+                groups.push((1, voter.clone()));
+            }
+        }
+    }
+    groups
+}
+
+/// Find the distinct ordinal ballots (as for plurality) the voters cast. Returns the distinct
+/// ballots in order of first appearance, along with the index into them of each voter's ballot,
+/// so that ballots\[indices\[v]] is the ballot of the voter at index v.
+pub fn distinct_ballots<T: Voter>(voters: &mut Vec<T>) -> (Vec<Vec<CandidateID>>, Vec<usize>) {
+    let mut ballots = Vec::new();
+    let mut index_of: HashMap<Vec<CandidateID>, usize> = HashMap::new();
+    let indices = voters
+        .iter_mut()
+        .map(|voter| {
+            let ballot = voter.cast_ordinal_ballot(OrdinalEnum::plurality);
+            match index_of.get(ballot) {
+                Some(&index) => index,
+                None => {
+                    index_of.insert(ballot.clone(), ballots.len());
+                    ballots.push(ballot.clone());
+                    ballots.len() - 1
+                }
+            }
+        })
+        .collect();
+    (ballots, indices)
+}
+
+/// Build the pairwise preference matrix from the ordinal ballots the voters cast for the given
+/// method, where matrix[i][j] is the number of voters who rank CandidateID(i) over CandidateID(j).
+/// A candidate ranked on a (truncated) ballot is preferred to any candidate left unranked; two
+/// unranked candidates are treated as equally preferred.
+pub fn pairwise_matrix<T: Voter>(
+    voters: &mut Vec<T>,
+    num_candidates: usize,
+    method: OrdinalEnum,
+) -> Vec<Vec<usize>> {
+    let mut matrix = vec![vec![0usize; num_candidates]; num_candidates];
+    for voter in voters {
+        let ballot = voter.cast_ordinal_ballot(method);
+        for (i, &CandidateID(higher)) in ballot.iter().enumerate() {
+            // Beats everyone ranked after them on the ballot
+            for &CandidateID(lower) in &ballot[(i + 1)..] {
+                matrix[higher][lower] += 1;
+            }
+            // Beats everyone left off the ballot
+            if ballot.len() < num_candidates {
+                (0..num_candidates)
+                    .filter(|&c| !ballot.contains(&CandidateID(c)))
+                    .for_each(|c| matrix[higher][c] += 1);
+            }
+        }
+    }
+    matrix
+}
+
+/// Build the pairwise preference matrix from the voters' sincere preferences rather than their
+/// ballots, where matrix[i][j] is the number of voters whose honest_preference is for
+/// CandidateID(i) over CandidateID(j). A voter who is indifferent between two candidates counts
+/// for neither. Since no ballots are cast, this gives the sincere pairwise outcomes even for voters
+/// whose ballots don't reflect their preferences (i.e. NoisyVoters). Requires that the voters
+/// contain utility information.
+pub fn utility_pairwise_matrix<T: Voter>(
+    voters: &Vec<T>,
+    num_candidates: usize,
+) -> Vec<Vec<usize>> {
+    let mut matrix = vec![vec![0usize; num_candidates]; num_candidates];
+    for voter in voters {
+        for (i, row) in matrix.iter_mut().enumerate() {
+            for (j, count) in row.iter_mut().enumerate() {
+                if voter.honest_preference(CandidateID(i), CandidateID(j)) == Ordering::Greater {
+                    *count += 1;
+                }
+            }
+        }
+    }
+    matrix
+}
+
+/// How pairwise_matrix_with_ties counts a ballot which ranks two candidates equally
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum TieCounting {
+    /// An equality expresses no preference between the two candidates
+    Ignore,
+    /// An equality counts as half a vote for each candidate over the other
+    Half,
+}
+
+/// Build the pairwise preference matrix from the ordinal-equal ballots the voters cast, so that
+/// candidates a voter ranks equally (i.e. gives the same score) are not forced into a strict
+/// order. Equalities are counted according to ties. So that half votes can be represented, the
+/// counts are in half votes: matrix[i][j] is twice the number of voters who rank CandidateID(i)
+/// over CandidateID(j), plus the number who rank them equally if ties is TieCounting::Half. Since
+/// every entry is doubled, the matrix can be passed directly to condorcet_winner, smith_set, etc.
+/// Candidates left off a ballot are ranked equally below every candidate on it.
+pub fn pairwise_matrix_with_ties<T: Voter>(
+    voters: &mut Vec<T>,
+    num_candidates: usize,
+    method_name: &str,
+    ties: TieCounting,
+) -> Vec<Vec<usize>> {
+    let tie_votes = match ties {
+        TieCounting::Ignore => 0,
+        TieCounting::Half => 1,
+    };
+    let mut matrix = vec![vec![0usize; num_candidates]; num_candidates];
+    for voter in voters {
+        let ballot = voter.cast_ordinal_equal_ballot(method_name);
+        // rank[c] is the position of the group containing CandidateID(c), with unranked
+        // candidates sharing a group after the last one
+        let mut rank = vec![ballot.len(); num_candidates];
+        for (position, group) in ballot.iter().enumerate() {
+            group.iter().for_each(|&CandidateID(c)| rank[c] = position);
+        }
+        for i in 0..num_candidates {
+            for j in 0..num_candidates {
+                if i == j {
+                    continue;
+                }
+                match rank[i].cmp(&rank[j]) {
+                    Ordering::Less => matrix[i][j] += 2,
+                    Ordering::Equal => matrix[i][j] += tie_votes,
+                    Ordering::Greater => {}
+                }
+            }
+        }
+    }
+    matrix
+}
+
+/// Build the full round-robin table from the ordinal ballots the voters cast for the given
+/// method: every head-to-head matchup ((a, b), (a_votes, b_votes)) with a < b, where a_votes is
+/// the number of voters who rank a over b and vice versa. Matchups are ordered by a, then b.
+/// Unranked candidates are handled as in pairwise_matrix.
+pub fn round_robin_results<T: Voter>(
+    voters: &mut Vec<T>,
+    num_candidates: usize,
+    method: OrdinalEnum,
+) -> Vec<((CandidateID, CandidateID), (usize, usize))> {
+    let matrix = pairwise_matrix(voters, num_candidates, method);
+    (0..num_candidates)
+        .flat_map(|a| ((a + 1)..num_candidates).map(move |b| (a, b)))
+        .map(|(a, b)| {
+            (
+                (CandidateID(a), CandidateID(b)),
+                (matrix[a][b], matrix[b][a]),
+            )
+        })
+        .collect()
+}
+
+/// Compute the effective number of candidates (the Laakso-Taagepera index) of the first
+/// preferences on the ordinal ballots the voters cast for the given method: 1 / sum(share_i^2),
+/// where share_i is CandidateID(i)'s share of first preferences. This measures how fragmented the
+/// field is, ranging from 1 (everyone has the same favorite) to num_candidates (an even split).
+pub fn effective_number_of_candidates<T: Voter>(
+    voters: &mut Vec<T>,
+    num_candidates: usize,
+    method: OrdinalEnum,
+) -> f64 {
+    let num_voters = voters.len() as f64;
+    let concentration = plurality_tally(voters, num_candidates, method)
+        .into_iter()
+        .map(|votes| (votes as f64 / num_voters).powi(2))
+        .sum::<f64>();
+    1f64 / concentration
+}
+
+/// Build the second-choice transfer matrix from the ordinal ballots the voters cast for the given
+/// method, where matrix[i][j] is the number of ballots ranking CandidateID(i) first and
+/// CandidateID(j) second. This reveals which candidates share support. Ballots ranking fewer than
+/// two candidates are not counted.
+pub fn transfer_matrix<T: Voter>(
+    voters: &mut Vec<T>,
+    num_candidates: usize,
+    method: OrdinalEnum,
+) -> Vec<Vec<usize>> {
+    let mut matrix = vec![vec![0usize; num_candidates]; num_candidates];
+    for voter in voters {
+        if let [CandidateID(first), CandidateID(second), ..] = voter.cast_ordinal_ballot(method)[..]
+        {
+            matrix[first][second] += 1;
+        }
+    }
+    matrix
+}
+
+/// Given a pairwise preference matrix (see pairwise_matrix), return the Condorcet winner (the
+/// candidate who beats every other candidate head-to-head), if one exists.
+pub fn condorcet_winner(matrix: &Vec<Vec<usize>>) -> Option<CandidateID> {
+    let n = matrix.len();
+    (0..n)
+        .find(|&i| (0..n).all(|j| i == j || matrix[i][j] > matrix[j][i]))
+        .map(CandidateID)
+}
+
+/// Given a pairwise preference matrix (see pairwise_matrix), return the Condorcet winner, if one
+/// exists, along with their smallest margin of victory (matrix[w][j] - matrix[j][w]) over any
+/// other candidate j. A small margin means few voters need to change their minds for the winner to
+/// lose their status. A lone candidate has no head-to-head contests, and a margin of 0.
+pub fn condorcet_winner_robustness(matrix: &Vec<Vec<usize>>) -> Option<(CandidateID, usize)> {
+    condorcet_winner(matrix).map(|winner| {
+        let CandidateID(w) = winner;
+        let margin = (0..matrix.len())
+            .filter(|&j| j != w)
+            .map(|j| matrix[w][j] - matrix[j][w])
+            .min()
+            .unwrap_or(0);
+        (winner, margin)
+    })
+}
+
+/// Given a pairwise preference matrix (see pairwise_matrix), return whether the pairwise defeats
+/// contain a cycle (i.e. A beats B, B beats C, and C beats A head-to-head).
+pub fn has_condorcet_cycle(matrix: &Vec<Vec<usize>>) -> bool {
+    let n = matrix.len();
+    // reaches[a][b] is whether a reaches b by a chain of pairwise wins
+    let mut reaches = (0..n)
+        .map(|a| (0..n).map(|b| matrix[a][b] > matrix[b][a]).collect::<Vec<_>>())
+        .collect::<Vec<_>>();
+    // Floyd-Warshall transitive closure
+    for k in 0..n {
+        for a in 0..n {
+            for b in 0..n {
+                reaches[a][b] = reaches[a][b] || (reaches[a][k] && reaches[k][b]);
+            }
+        }
+    }
+    (0..n).any(|a| reaches[a][a])
+}
+
+/// Given a pairwise preference matrix (see pairwise_matrix), return the Smith set: the smallest
+/// non-empty set of candidates such that every candidate in the set beats every candidate outside
+/// of it head-to-head. Candidates are returned in increasing order of their IDs.
+pub fn smith_set(matrix: &Vec<Vec<usize>>) -> Vec<CandidateID> {
+    let candidates = (0..matrix.len()).collect::<Vec<_>>();
+    smith_subset(matrix, &candidates)
+        .into_iter()
+        .map(CandidateID)
+        .collect()
+}
+
+/// Compute the Smith set among only the given candidates. A candidate is in the Smith set exactly
+/// when they can reach every other candidate by a chain of pairwise wins or ties, since no
+/// candidate outside the Smith set beats or ties anyone inside it.
+pub(crate) fn smith_subset(matrix: &Vec<Vec<usize>>, candidates: &[usize]) -> Vec<usize> {
+    let n = candidates.len();
+    // reaches[a][b] is whether candidates[a] reaches candidates[b]
+    let mut reaches = (0..n)
+        .map(|a| {
+            (0..n)
+                .map(|b| matrix[candidates[a]][candidates[b]] >= matrix[candidates[b]][candidates[a]])
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+    // Floyd-Warshall transitive closure
+    for k in 0..n {
+        for a in 0..n {
+            for b in 0..n {
+                reaches[a][b] = reaches[a][b] || (reaches[a][k] && reaches[k][b]);
+            }
+        }
+    }
+    (0..n)
+        .filter(|&a| reaches[a].iter().all(|&r| r))
+        .map(|a| candidates[a])
+        .collect()
+}
+
+/// Find the Condorcet winner directly from the voters' ordinal ballots without building the full
+/// pairwise matrix, which is wasteful when there are many candidates. Works in the style of the
+/// Boyer-Moore majority vote algorithm: a single pass keeps a surviving candidate, replacing them
+/// whenever the next candidate beats them head-to-head, and a second pass verifies the survivor
+/// beats every other candidate. Only the survivor can possibly be the Condorcet winner, since the
+/// Condorcet winner would have replaced (and never been replaced by) any other survivor.
+/// Truncated ballots are treated the same as in pairwise_matrix.
+pub fn condorcet_winner_fast<T: Voter>(
+    voters: &mut Vec<T>,
+    num_candidates: usize,
+    method: OrdinalEnum,
+) -> Option<CandidateID> {
+    if num_candidates == 0 {
+        return None;
+    }
+
+    // Position of each candidate on each ballot; unranked candidates are placed at usize::MAX
+    let positions = voters
+        .iter_mut()
+        .map(|v| {
+            let mut position = vec![usize::MAX; num_candidates];
+            v.cast_ordinal_ballot(method)
+                .iter()
+                .enumerate()
+                .for_each(|(i, &CandidateID(id))| position[id] = i);
+            position
+        })
+        .collect::<Vec<_>>();
+
+    // Whether candidate a beats candidate b head-to-head
+    let beats = |a: usize, b: usize| {
+        let (a_wins, b_wins) = positions
+            .iter()
+            .fold((0usize, 0usize), |(a_wins, b_wins), position| {
+                match position[a].cmp(&position[b]) {
+                    Ordering::Less => (a_wins + 1, b_wins),
+                    Ordering::Equal => (a_wins, b_wins),
+                    Ordering::Greater => (a_wins, b_wins + 1),
+                }
+            });
+        a_wins > b_wins
+    };
+
+    // Elimination pass
+    let survivor = (1..num_candidates).fold(0, |survivor, challenger| {
+        if beats(challenger, survivor) {
+            challenger
+        } else {
+            survivor
+        }
+    });
+
+    // Verification pass
+    if (0..num_candidates).all(|c| c == survivor || beats(survivor, c)) {
+        Some(CandidateID(survivor))
+    } else {
+        None
+    }
+}
+
+/// Check whether the voters' ordinal ballots (cast as for plurality) are single-peaked: whether
+/// there is some ordering of the candidates along an axis such that every voter's preference
+/// rises to their favorite and then falls as the axis is traversed. Returns such an axis if one
+/// exists (its reverse is then also one), or None otherwise.
+///
+/// The axis is built from the outside in, in the style of Escoffier, Lang and Öztürk: each voter's
+/// least favorite of the candidates not yet placed must lie at one end of the unplaced stretch,
+/// and each newly placed candidate must be consistent with its placed neighbor for every voter.
+/// This settles all but a few placements outright; the rest are tried both ways, so the search
+/// can backtrack, though only rarely. Panics if a ballot doesn't rank every candidate.
+pub fn is_single_peaked<T: Voter>(
+    voters: &mut Vec<T>,
+    num_candidates: usize,
+) -> Option<Vec<CandidateID>> {
+    // positions[v][c] is the position of CandidateID(c) on voter v's ballot
+    let positions = voters
+        .iter_mut()
+        .map(|v| {
+            let ballot = v.cast_ordinal_ballot(OrdinalEnum::plurality);
+            if ballot.len() != num_candidates {
+                panic!("{}", COMPLETE_BALLOT_WARNING)
+            }
+            let mut position = vec![0; num_candidates];
+            ballot
+                .iter()
+                .enumerate()
+                .for_each(|(i, &CandidateID(id))| position[id] = i);
+            position
+        })
+        .collect::<Vec<_>>();
+
+    // Whether inner can be placed next to outer, the innermost candidate placed so far on one
+    // side: every voter whose favorite is on that side must prefer outer, and every other voter
+    // must prefer inner
+    let fits = |side: &[usize], outer: usize, inner: usize| {
+        positions.iter().all(|position| {
+            let favorite = (0..num_candidates).find(|&c| position[c] == 0).unwrap();
+            side.contains(&favorite) == (position[outer] < position[inner])
+        })
+    };
+
+    fn place<P: Fn(&[usize], usize, usize) -> bool>(
+        positions: &[Vec<usize>],
+        fits: &P,
+        left: &mut Vec<usize>,
+        right: &mut Vec<usize>,
+        remaining: &[usize],
+    ) -> bool {
+        if remaining.is_empty() {
+            return match (left.last(), right.last()) {
+                (Some(&l), Some(&r)) => fits(left, l, r),
+                _ => true,
+            };
+        }
+
+        let mut bottoms = positions
+            .iter()
+            .map(|position| *remaining.iter().max_by_key(|&&c| position[c]).unwrap())
+            .collect::<Vec<_>>();
+        bottoms.sort_unstable();
+        bottoms.dedup();
+        let mut options = match bottoms[..] {
+            [x] => vec![(Some(x), None), (None, Some(x))],
+            [x, y] => vec![(Some(x), Some(y)), (Some(y), Some(x))],
+            _ => return false,
+        };
+        if left.is_empty() && right.is_empty() {
+            // The axis can be mirrored, so the first placement needs only one orientation
+            options.truncate(1);
+        }
+
+        for (to_left, to_right) in options {
+            let fits_side =
+                |side: &Vec<usize>, candidate: Option<usize>| match (side.last(), candidate) {
+                    (Some(&outer), Some(inner)) => fits(side, outer, inner),
+                    _ => true,
+                };
+            if !fits_side(left, to_left) || !fits_side(right, to_right) {
+                continue;
+            }
+
+            left.extend(to_left);
+            right.extend(to_right);
+            let rest = remaining
+                .iter()
+                .copied()
+                .filter(|&c| Some(c) != to_left && Some(c) != to_right)
+                .collect::<Vec<_>>();
+            if place(positions, fits, left, right, &rest) {
+                return true;
+            }
+            left.truncate(left.len() - to_left.iter().count());
+            right.truncate(right.len() - to_right.iter().count());
+        }
+        false
+    }
+
+    if positions.is_empty() {
+        return Some(generate_candidates(num_candidates));
+    }
+    let (mut left, mut right) = (Vec::new(), Vec::new());
+    let candidates = (0..num_candidates).collect::<Vec<_>>();
+    if place(&positions, &fits, &mut left, &mut right, &candidates) {
+        left.extend(right.into_iter().rev());
+        Some(left.into_iter().map(CandidateID).collect())
+    } else {
+        None
+    }
+}
+
+/// Unit tests for this module
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::election::election_methods::tests::*;
+    use crate::election::voters::ApprovalThresholdBehavior::Mean;
+    use crate::election::voters::RealCardinalVoter;
+    use crate::utility_generators::condorcet_cycle_electorate;
+
+    #[test]
+    fn test_utility_ranking() {
+        assert_eq!(
+            utility_ranking(&majority_election(), 3, higher_index_wins()),
+            vec![CandidateID(2), CandidateID(1), CandidateID(0)]
+        );
+    }
+
+    #[test]
+    fn test_per_voter_regret() {
+        let regrets = per_voter_regret(&majority_election(), CandidateID(2));
+        assert_eq!(regrets.len(), 3);
+        assert_eq!(regrets[0], 0.0);
+        assert_eq!(regrets[1], 0.0);
+        assert!((regrets[2] - 0.5).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_disagreement_matrix() {
+        // A center squeeze: B is squeezed out of plurality and the runoff, which A wins, but is
+        // everyone's first or second choice so wins the Borda count
+        let mut voters = vec![HonestVoter::new(vec![1.0, 0.5, 0.0], false, Mean); 5];
+        voters.extend(vec![HonestVoter::new(vec![0.0, 0.5, 1.0], false, Mean); 4]);
+        voters.extend(vec![HonestVoter::new(vec![0.5, 1.0, 0.0], false, Mean); 3]);
+        let matrix = disagreement_matrix(
+            &mut voters,
+            3,
+            higher_index_wins(),
+            &[
+                &ElectionMethods::plurality,
+                &ElectionMethods::borda,
+                &ElectionMethods::fptp_runoff,
+            ],
+        );
+        assert_eq!(
+            matrix,
+            vec![
+                vec![false, true, false],
+                vec![true, false, true],
+                vec![false, true, false]
+            ]
+        );
+    }
+
+    /*
+    Profile produced:
+    2: A > B > C
+    1: B > C > A
+    1: C > A > B
+     */
+    fn no_show() -> Vec<HonestVoter> {
+        vec![
+            HonestVoter::new(vec![1.0, 0.5, 0.0], false, Mean),
+            HonestVoter::new(vec![1.0, 0.5, 0.0], false, Mean),
+            HonestVoter::new(vec![0.0, 1.0, 0.5], false, Mean),
+            HonestVoter::new(vec![0.5, 0.0, 1.0], false, Mean),
+        ]
+    }
+
+    #[test]
+    fn test_ranking_quality() {
+        // Borda reproduces the utility order C > B > A exactly
+        assert_eq!(
+            ranking_quality(
+                &mut majority_election(),
+                3,
+                higher_index_wins(),
+                ElectionMethods::borda
+            ),
+            1.0
+        );
+        let reversed = |voters: &mut Vec<HonestVoter>, n, tie_breaker| {
+            let mut ranking = utility_ranking(voters, n, tie_breaker);
+            ranking.reverse();
+            ranking
+        };
+        assert_eq!(
+            ranking_quality(&mut majority_election(), 3, higher_index_wins(), reversed),
+            -1.0
+        );
+    }
+
+    #[test]
+    fn test_participation_violated() {
+        // Without the new voter, C wins IRV; a new B > C > A voter causes A to win instead
+        let new_voter = HonestVoter::new(vec![0.0, 1.0, 0.5], false, Mean);
+        assert_eq!(
+            ElectionMethods::irv(&mut no_show(), 3, higher_index_wins())[0],
+            CandidateID(2)
+        );
+        assert!(participation_violated(
+            &no_show(),
+            3,
+            higher_index_wins(),
+            ElectionMethods::irv,
+            new_voter.clone()
+        ));
+        assert!(!participation_violated(
+            &no_show(),
+            3,
+            higher_index_wins(),
+            ElectionMethods::plurality,
+            new_voter
+        ));
+    }
+
+    #[test]
+    fn test_later_no_harm_violated() {
+        // A > D > C and a bullet vote for C: C wins Borda with 4 points to A's 3, but once the
+        // second voter adds A below C, A wins with 5 points
+        let voters = vec![
+            RealOrdinalVoter::new(vec![CandidateID(0), CandidateID(3), CandidateID(2)]),
+            RealOrdinalVoter::new(vec![CandidateID(2)]),
+        ];
+        assert!(later_no_harm_violated(
+            &voters,
+            4,
+            higher_index_wins(),
+            ElectionMethods::borda,
+            1,
+            CandidateID(0)
+        ));
+        assert!(!later_no_harm_violated(
+            &voters,
+            4,
+            higher_index_wins(),
+            ElectionMethods::irv,
+            1,
+            CandidateID(0)
+        ));
+
+        // The same profile from utilities, where the second voter ranks C > A > D > B, so their
+        // ballot is truncated just above A
+        let voters = vec![
+            HonestVoter::new(vec![1.0, 0.0, 0.4, 0.7], false, Mean),
+            HonestVoter::new(vec![0.6, 0.2, 1.0, 0.4], false, Mean),
+        ];
+        assert!(later_no_harm_violated(
+            &voters,
+            4,
+            higher_index_wins(),
+            ElectionMethods::borda,
+            1,
+            CandidateID(0)
+        ));
+        assert!(!later_no_harm_violated(
+            &voters,
+            4,
+            higher_index_wins(),
+            ElectionMethods::irv,
+            1,
+            CandidateID(0)
+        ));
+    }
+
+    #[test]
+    fn test_irv_compromise_incentive() {
+        // Center squeeze: B is everyone's first or second choice, but is eliminated first, after
+        // which C beats A 11 to 9. Any one A > B > C voter ranking B first would instead knock out
+        // A, letting B win 13 to 7.
+        let mut voters = Vec::new();
+        for (utilities, count) in [
+            (vec![1.0, 0.5, 0.0], 7),
+            (vec![0.0, 1.0, 0.5], 4),
+            (vec![0.5, 1.0, 0.0], 2),
+            (vec![0.0, 0.5, 1.0], 7),
+        ] {
+            (0..count).for_each(|_| voters.push(HonestVoter::new(utilities.clone(), false, Mean)));
+        }
+        assert_eq!(
+            ElectionMethods::irv(&mut voters.clone(), 3, higher_index_wins())[0],
+            CandidateID(2)
+        );
+        assert_eq!(irv_compromise_incentive(&voters, 3, higher_index_wins()), 7);
+    }
+
+    #[test]
+    fn test_round_robin_results() {
+        let results = round_robin_results(&mut majority_election(), 3, OrdinalEnum::plurality);
+        assert_eq!(results.len(), 3);
+        assert_eq!(results[0], ((CandidateID(0), CandidateID(1)), (1, 2)));
+        assert!(results.iter().all(|&(_, (a, b))| a + b == 3));
+
+        // 5 choose 2 matchups
+        let mut voters = vec![HonestVoter::new(vec![0.1, 0.2, 0.3, 0.4, 0.5], false, Mean)];
+        assert_eq!(
+            round_robin_results(&mut voters, 5, OrdinalEnum::plurality).len(),
+            10
+        );
+    }
+
+    #[test]
+    fn test_favorite_betrayal_incentive() {
+        // A wins plurality 4 to 3 to 2. The C > B > A voters can elect B instead by abandoning C,
+        // but under approval they already approve B as well as C, so B wins without betrayal.
+        let mut voters = Vec::new();
+        for (utilities, count) in [
+            (vec![1.0, 0.3, 0.0], 4),
+            (vec![0.2, 1.0, 0.1], 3),
+            (vec![0.0, 0.8, 1.0], 2),
+        ] {
+            (0..count).for_each(|_| voters.push(HonestVoter::new(utilities.clone(), false, Mean)));
+        }
+        let plurality = favorite_betrayal_incentive(
+            &voters,
+            3,
+            higher_index_wins(),
+            ElectionMethods::plurality,
+        );
+        let approval =
+            favorite_betrayal_incentive(&voters, 3, higher_index_wins(), ElectionMethods::approval);
+        assert_eq!(plurality, 2);
+        assert_eq!(approval, 0);
+
+        let voters = voters.into_iter().map(Voters::from).collect::<Vec<_>>();
+        let plurality = favorite_betrayal_incentive(
+            &voters,
+            3,
+            higher_index_wins(),
+            ElectionMethods::plurality,
+        );
+        assert_eq!(plurality, 2);
+    }
+
+    #[test]
+    fn test_bootstrap_winner_stability() {
+        use rand::rngs::StdRng;
+        use rand::SeedableRng;
+
+        let mut voters = vec![HonestVoter::new(vec![1.0, 0.5, 0.0], false, Mean); 9];
+        voters.push(HonestVoter::new(vec![0.0, 0.5, 1.0], false, Mean));
+        let stability = bootstrap_winner_stability(
+            &mut StdRng::seed_from_u64(681),
+            &voters,
+            3,
+            higher_index_wins(),
+            ElectionMethods::plurality,
+            200,
+        );
+        assert_eq!(stability.len(), 3);
+        assert!(stability[&CandidateID(0)] > 0.95);
+        assert_eq!(stability[&CandidateID(1)], 0.0);
+        assert!((stability.values().sum::<f64>() - 1.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_voter_satisfaction_efficiency() {
+        // Total utilities are A: 0.9, B: 1.5, C: 1.6, averaging 4/3
+        let voters = majority_election();
+        assert!((voter_satisfaction_efficiency(&voters, CandidateID(2)) - 1.0).abs() < 1e-12);
+        assert!((voter_satisfaction_efficiency(&voters, CandidateID(1)) - 0.625).abs() < 1e-12);
+        assert!((voter_satisfaction_efficiency(&voters, CandidateID(0)) + 1.625).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_committee_utility() {
+        // Two voters like A and B, and one likes only C
+        let voters = vec![
+            HonestVoter::new(vec![1.0, 0.9, 0.0], false, Mean),
+            HonestVoter::new(vec![1.0, 0.9, 0.0], false, Mean),
+            HonestVoter::new(vec![0.0, 0.0, 1.0], false, Mean),
+        ];
+        let (good, poor) = (
+            [CandidateID(0), CandidateID(2)],
+            [CandidateID(0), CandidateID(1)],
+        );
+        assert_eq!(committee_utility(&voters, &good), 3.0);
+        assert_eq!(committee_utility(&voters, &poor), 2.0);
+        assert_eq!(proportional_committee_utility(&voters, &good), 3.0);
+        assert!((proportional_committee_utility(&voters, &poor) - 2.9).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_run_on_utilities() {
+        let utilities = vec![
+            vec![0.9, 0.5, 0.0],
+            vec![0.8, 0.6, 0.1],
+            vec![0.0, 0.7, 1.0],
+        ];
+        let run = |method: fn(&mut Vec<HonestVoter>, usize, _) -> Vec<CandidateID>| {
+            run_on_utilities(&utilities, 3, false, Mean, higher_index_wins(), method)
+        };
+        assert_eq!(
+            run(ElectionMethods::plurality),
+            vec![CandidateID(0), CandidateID(2), CandidateID(1)]
+        );
+        // Approval ballots are {A, B}, {A, B} and {B, C}
+        assert_eq!(
+            run(ElectionMethods::approval),
+            vec![CandidateID(1), CandidateID(0), CandidateID(2)]
+        );
+    }
+
+    #[test]
+    fn test_exaggeration_benefit() {
+        // Scores are A 19 and B 21, so B beats A unless the first voter min-maxes them
+        let voters = vec![
+            HonestVoter::new(vec![1.0, 0.6, 0.0], false, Mean),
+            HonestVoter::new(vec![0.5, 1.0, 0.0], false, Mean),
+            HonestVoter::new(vec![0.4, 0.5, 0.0], false, Mean),
+        ];
+        let benefit = |voter_index, method: fn(&mut Vec<_>, usize, _) -> Vec<CandidateID>| {
+            exaggeration_benefit(&voters, 3, voter_index, 10, higher_index_wins(), method)
+        };
+        assert!((benefit(0, ElectionMethods::score_10) - 0.4).abs() < 1e-9);
+        assert_eq!(benefit(1, ElectionMethods::score_10), 0.0);
+        // The exaggeration still sends A to the STAR runoff, which B wins 2 to 1
+        assert_eq!(benefit(0, ElectionMethods::star_10), 0.0);
+
+        // With one candidate there is nothing to exaggerate
+        let voters = vec![HonestVoter::new(vec![0.7], false, Mean)];
+        let benefit =
+            exaggeration_benefit(&voters, 1, 0, 10, higher_index_wins(), ElectionMethods::score_10);
+        assert_eq!(benefit, 0.0);
+    }
+
+    #[test]
+    fn test_strategy_impact() {
+        use crate::utility_generators::uniform_utilities;
+        use rand::rngs::StdRng;
+        use rand::SeedableRng;
+
+        let setup = StrategyTrials {
+            trials: 20,
+            num_voters: 25,
+            num_candidates: 4,
+            strategic_fraction: 0.5,
+        };
+        let (honest, strategic) = strategy_impact(
+            &mut StdRng::seed_from_u64(690),
+            setup,
+            uniform_utilities,
+            higher_index_wins(),
+            ElectionMethods::score_10,
+        );
+        assert!(honest.is_finite());
+        assert!(strategic.is_finite());
+
+        // With one candidate there are no frontrunners to min-max, and the winner is ideal
+        let setup = StrategyTrials {
+            num_candidates: 1,
+            ..setup
+        };
+        let impact = strategy_impact(
+            &mut StdRng::seed_from_u64(690),
+            setup,
+            uniform_utilities,
+            higher_index_wins(),
+            ElectionMethods::score_10,
+        );
+        assert_eq!(impact, (1.0, 1.0));
+    }
+
+    #[test]
+    fn test_strategic_winner_change_rate() {
+        use crate::utility_generators::uniform_utilities;
+        use rand::rngs::StdRng;
+        use rand::SeedableRng;
+
+        let setup = StrategyTrials {
+            trials: 50,
+            num_voters: 25,
+            num_candidates: 4,
+            strategic_fraction: 0.5,
+        };
+        let rate = |setup, method: fn(&mut Vec<Voters>, usize, _) -> Vec<CandidateID>| {
+            strategic_winner_change_rate(
+                &mut StdRng::seed_from_u64(731),
+                setup,
+                uniform_utilities,
+                higher_index_wins(),
+                method,
+            )
+        };
+        let score_rate = rate(setup, ElectionMethods::score_10);
+        assert!(score_rate > 0.0 && score_rate <= 1.0);
+        // Strategic voters cast sincere ordinal ballots
+        assert_eq!(rate(setup, ElectionMethods::irv), 0.0);
+        let setup = StrategyTrials {
+            num_candidates: 1,
+            ..setup
+        };
+        assert_eq!(rate(setup, ElectionMethods::score_10), 0.0);
+    }
+
+    #[test]
+    fn test_borda_condorcet_agreement() {
+        use crate::utility_generators::uniform_utilities;
+        use rand::rngs::StdRng;
+        use rand::SeedableRng;
+
+        let rate = borda_condorcet_agreement(
+            &mut StdRng::seed_from_u64(710),
+            50,
+            15,
+            4,
+            uniform_utilities,
+            higher_index_wins(),
+        );
+        assert!((0.0..=1.0).contains(&rate));
+    }
+
+    #[test]
+    fn test_condorcet_efficiency() {
+        use crate::metrics::lp_metric;
+        use crate::utility_generators::{SpatialModel, UtilityModel};
+        use rand::rngs::StdRng;
+        use rand::SeedableRng;
+        use rand_distr::Normal;
+
+        // A centrist squeezed between two candidates on either side
+        let model = SpatialModel {
+            candidate_locations: vec![vec![-1.0], vec![-0.7], vec![0.0], vec![0.7], vec![1.0]],
+            voter_distributions: vec![Normal::new(0.0, 1.0).unwrap()],
+            metric: lp_metric::<2>,
+        };
+        let mut gen_utilities = |rng: &mut StdRng, n| model.generate(rng, n);
+        let plurality = condorcet_efficiency(
+            &mut StdRng::seed_from_u64(717),
+            100,
+            51,
+            5,
+            &mut gen_utilities,
+            higher_index_wins(),
+            ElectionMethods::plurality,
+        );
+        let runoff = condorcet_efficiency(
+            &mut StdRng::seed_from_u64(717),
+            100,
+            51,
+            5,
+            &mut gen_utilities,
+            higher_index_wins(),
+            ElectionMethods::fptp_runoff,
+        );
+        assert!(plurality < runoff);
+    }
+
+    #[test]
+    fn test_smith_set_size_distribution() {
+        use crate::utility_generators::uniform_utilities;
+        use rand::rngs::StdRng;
+        use rand::SeedableRng;
+
+        let sizes = smith_set_size_distribution(
+            &mut StdRng::seed_from_u64(713),
+            100,
+            25,
+            4,
+            uniform_utilities,
+        );
+        assert_eq!(sizes.values().sum::<usize>(), 100);
+        assert!(sizes.keys().all(|&size| (1..=4).contains(&size)));
+        assert!(sizes[&1] > 50);
+    }
+
+    #[test]
+    fn test_finish_range() {
+        // B is everyone's compromise, but has the fewest first preferences
+        let mut voters = vec![HonestVoter::new(vec![1.0, 0.6, 0.0], false, Mean); 35];
+        voters.extend(vec![HonestVoter::new(vec![0.5, 1.0, 0.4], false, Mean); 30]);
+        voters.extend(vec![HonestVoter::new(vec![0.0, 0.6, 1.0], false, Mean); 35]);
+        let ranges = finish_range(&mut voters, 3, higher_index_wins());
+        assert_eq!(ranges[1], (1, 3));
+    }
+
+    #[test]
+    fn test_compress_electorate() {
+        let compressed = compress_electorate(&mut irv_differs());
+        let weights = compressed
+            .iter()
+            .map(|(weight, _)| *weight)
+            .collect::<Vec<_>>();
+        assert_eq!(weights, vec![24, 24, 20, 20, 12]);
+        for (weight, mut voter) in compressed {
+            let ballot = voter.cast_ordinal_ballot(OrdinalEnum::plurality).clone();
+            let matching = irv_differs()
+                .iter_mut()
+                .map(|v| v.cast_ordinal_ballot(OrdinalEnum::plurality))
+                .filter(|&cast| cast == &ballot)
+                .count();
+            assert_eq!(matching, weight);
+        }
+    }
+
+    #[test]
+    fn test_distinct_ballots() {
+        let mut voters = irv_differs();
+        let (ballots, indices) = distinct_ballots(&mut voters);
+        assert_eq!(ballots.len(), 5);
+        assert_eq!(indices.len(), 100);
+        // The first 48 voters alternate between the first two ballots, and the next 40 between the
+        // third and fourth
+        for (v, &index) in indices.iter().enumerate() {
+            let expected = match v {
+                0..=47 => v % 2,
+                48..=87 => 2 + v % 2,
+                _ => 4,
+            };
+            assert_eq!(index, expected);
+            assert_eq!(
+                &ballots[index],
+                voters[v].cast_ordinal_ballot(OrdinalEnum::plurality)
+            );
+        }
+    }
+
+    #[test]
+    fn test_timed_run() {
+        let (ranking, duration) = timed_run(
+            &mut irv_differs(),
+            5,
+            higher_index_wins(),
+            ElectionMethods::irv,
+        );
+        assert_eq!(
+            ranking,
+            ElectionMethods::irv(&mut irv_differs(), 5, higher_index_wins())
+        );
+        assert!(duration > Duration::ZERO);
+    }
+
+    #[test]
+    fn test_approval_threshold_sweep() {
+        use crate::utility_generators::uniform_utilities;
+        use rand::rngs::StdRng;
+        use rand::SeedableRng;
+
+        let thresholds = [0.2, 0.5, 0.8];
+        let vses = approval_threshold_sweep(
+            &mut StdRng::seed_from_u64(719),
+            20,
+            25,
+            4,
+            uniform_utilities,
+            &thresholds,
+            higher_index_wins(),
+        );
+        assert_eq!(vses.len(), thresholds.len());
+        assert!(vses.iter().all(|vse| vse.is_finite()));
+    }
+
+    #[test]
+    fn test_is_single_peaked() {
+        // Single-peaked on the axis A, B, C, D
+        let ballot = |ids: [usize; 4]| RealOrdinalVoter::new(ids.map(CandidateID).to_vec());
+        let mut voters = vec![
+            ballot([1, 2, 0, 3]),
+            ballot([2, 3, 1, 0]),
+            ballot([0, 1, 2, 3]),
+            ballot([3, 2, 1, 0]),
+        ];
+        let axis = is_single_peaked(&mut voters, 4).unwrap();
+        let expected = (0..4).map(CandidateID).collect::<Vec<_>>();
+        let reversed = expected.iter().rev().copied().collect::<Vec<_>>();
+        assert!(axis == expected || axis == reversed);
+
+        // Every candidate is someone's least favorite, so none can be in the middle of an axis
+        assert_eq!(
+            is_single_peaked(&mut condorcet_cycle_electorate(1), 3),
+            None
+        );
+    }
+
+    #[test]
+    fn test_min_manipulating_coalition() {
+        // B leads A 6 to 4 in first preferences. One B voter switching to A only produces a tie,
+        // which B wins, so it takes two.
+        let mut voters = vec![HonestVoter::new(vec![0.5, 1.0, 0.0], false, Mean); 6];
+        voters.extend(vec![HonestVoter::new(vec![1.0, 0.5, 0.0], false, Mean); 4]);
+        voters.extend(vec![HonestVoter::new(vec![0.0, 0.5, 1.0], false, Mean); 3]);
+        let coalition = |target| {
+            min_manipulating_coalition(
+                &voters,
+                3,
+                higher_index_wins(),
+                ElectionMethods::plurality,
+                target,
+            )
+        };
+        assert_eq!(coalition(CandidateID(1)), Some(0));
+        assert_eq!(coalition(CandidateID(0)), Some(2));
+    }
+
+    #[test]
+    fn test_monte_carlo_winner() {
+        use crate::election::voters::NoisyVoter;
+        use rand::rngs::StdRng;
+        use rand::SeedableRng;
+
+        // Everyone truly prefers A, but not by enough to always perceive it
+        let mut voters = (0..5)
+            .map(|i| {
+                Voters::from(NoisyVoter::new(
+                    vec![0.6, 0.5, 0.1],
+                    false,
+                    Mean,
+                    0.1,
+                    StdRng::seed_from_u64(i),
+                ))
+            })
+            .collect::<Vec<_>>();
+        let frequencies = monte_carlo_winner(
+            &mut voters,
+            3,
+            higher_index_wins(),
+            ElectionMethods::plurality,
+            200,
+        );
+        assert!(frequencies[&CandidateID(0)] > 0.5);
+        assert!(frequencies[&CandidateID(0)] < 1.0);
+        assert!(frequencies[&CandidateID(0)] > frequencies[&CandidateID(1)]);
+        assert!((frequencies.values().sum::<f64>() - 1.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_approval_best_response_iteration() {
+        // Sincerely, B and C tie with 2 approvals each and C wins the tie. The B > C > A voter can
+        // elect B instead by withdrawing their approval of C, after which nobody can do better.
+        let mut voters = vec![
+            HonestVoter::new(vec![0.2, 0.0, 1.0], false, Mean),
+            HonestVoter::new(vec![0.0, 1.0, 0.8], false, Mean),
+            HonestVoter::new(vec![0.5, 1.0, 0.0], false, Mean),
+        ];
+        assert_eq!(
+            ElectionMethods::approval(&mut voters.clone(), 3, higher_index_wins())[0],
+            CandidateID(2)
+        );
+        let converged = approval_best_response_iteration(&mut voters, 3, higher_index_wins(), 2);
+        assert_eq!(converged[0], CandidateID(1));
+        assert_eq!(
+            converged,
+            approval_best_response_iteration(&mut voters, 3, higher_index_wins(), 100)
+        );
+    }
+
+    #[test]
+    fn test_effective_number_of_candidates() {
+        // Two candidates split 49 / 49, with 2 votes for a third
+        let mut two_party = vec![HonestVoter::new(vec![1.0, 0.5, 0.0], false, Mean); 49];
+        two_party.extend(vec![HonestVoter::new(vec![0.0, 0.5, 1.0], false, Mean); 49]);
+        two_party.extend(vec![HonestVoter::new(vec![0.5, 1.0, 0.0], false, Mean); 2]);
+        let effective = effective_number_of_candidates(&mut two_party, 3, OrdinalEnum::plurality);
+        assert!((effective - 2.0).abs() < 0.1);
+
+        // An even split between all 3 candidates
+        let mut even = condorcet_cycle_electorate(5);
+        let effective = effective_number_of_candidates(&mut even, 3, OrdinalEnum::plurality);
+        assert!((effective - 3.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_polarization() {
+        use crate::utility_generators::{polarized_utilities, uniform_utilities};
+        use rand::rngs::StdRng;
+        use rand::SeedableRng;
+
+        let mut rng = StdRng::seed_from_u64(687);
+        let polarized = (0..500)
+            .map(|_| HonestVoter::new(polarized_utilities(&mut rng, 4), false, Mean))
+            .collect::<Vec<_>>();
+        let uniform = (0..500)
+            .map(|_| HonestVoter::new(uniform_utilities(&mut rng, 4), false, Mean))
+            .collect::<Vec<_>>();
+        assert!(polarization(&polarized, 4) > polarization(&uniform, 4));
+
+        // Unanimous electorates aren't polarized at all
+        let unanimous = vec![HonestVoter::new(vec![0.2, 0.9, 0.5], false, Mean); 10];
+        assert!(polarization(&unanimous, 3).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_pairwise_matrix_with_ties() {
+        // A and B tie at the top of the ballot, so the strict ballot ranks B over A
+        let voter = RealCardinalVoter::new(10, vec![10, 10, 3], higher_index_wins());
+        let strict = pairwise_matrix(&mut vec![voter.clone()], 3, OrdinalEnum::plurality);
+        assert_eq!((strict[0][1], strict[1][0]), (0, 1));
+
+        let ignored =
+            pairwise_matrix_with_ties(&mut vec![voter.clone()], 3, "test", TieCounting::Ignore);
+        assert_eq!((ignored[0][1], ignored[1][0]), (0, 0));
+        assert_eq!((ignored[0][2], ignored[1][2], ignored[2][0]), (2, 2, 0));
+
+        let halved = pairwise_matrix_with_ties(&mut vec![voter], 3, "test", TieCounting::Half);
+        assert_eq!((halved[0][1], halved[1][0]), (1, 1));
+        assert_eq!(condorcet_winner(&halved), None);
+    }
+
+    #[test]
+    fn test_utility_pairwise_matrix() {
+        use crate::election::voters::NoisyVoter;
+        use rand::rngs::StdRng;
+        use rand::SeedableRng;
+
+        assert_eq!(
+            utility_pairwise_matrix(&majority_election(), 3),
+            pairwise_matrix(&mut majority_election(), 3, OrdinalEnum::plurality)
+        );
+
+        // Redraw until the voter misperceives their preferences, so their ballot isn't sincere
+        let mut voter = NoisyVoter::new(
+            vec![0.5, 0.45, 0.4],
+            false,
+            Mean,
+            0.2,
+            StdRng::seed_from_u64(736),
+        );
+        let sincere = vec![CandidateID(0), CandidateID(1), CandidateID(2)];
+        while voter.cast_ordinal_ballot(OrdinalEnum::plurality) == &sincere {
+            voter.redraw_ballots();
+        }
+        let mut voters = vec![voter];
+        let matrix = utility_pairwise_matrix(&voters, 3);
+        assert_eq!(matrix, vec![vec![0, 1, 1], vec![0, 0, 1], vec![0, 0, 0]]);
+        assert_ne!(
+            pairwise_matrix(&mut voters, 3, OrdinalEnum::plurality),
+            matrix
+        );
+    }
+
+    #[test]
+    fn test_condorcet_winner() {
+        let matrix = pairwise_matrix(&mut majority_election(), 3, OrdinalEnum::plurality);
+        assert_eq!(matrix[2][0], 2);
+        assert_eq!(matrix[0][2], 1);
+        assert_eq!(condorcet_winner(&matrix), Some(CandidateID(2)));
+        let matrix = pairwise_matrix(
+            &mut condorcet_cycle_electorate(1),
+            3,
+            OrdinalEnum::plurality,
+        );
+        assert_eq!(condorcet_winner(&matrix), None);
+    }
+
+    #[test]
+    fn test_condorcet_winner_robustness() {
+        let matrix = pairwise_matrix(&mut majority_election(), 3, OrdinalEnum::plurality);
+        let weakest = (0..2).map(|j| matrix[2][j] - matrix[j][2]).min().unwrap();
+        assert_eq!(weakest, 1);
+        assert_eq!(
+            condorcet_winner_robustness(&matrix),
+            Some((CandidateID(2), weakest))
+        );
+        let matrix = pairwise_matrix(
+            &mut condorcet_cycle_electorate(1),
+            3,
+            OrdinalEnum::plurality,
+        );
+        assert_eq!(condorcet_winner_robustness(&matrix), None);
+    }
+
+    #[test]
+    fn test_transfer_matrix() {
+        let matrix = transfer_matrix(&mut irv_differs(), 5, OrdinalEnum::irv);
+        assert_eq!(matrix[0][1], 24);
+        assert_eq!(matrix[1][0], 24);
+        assert_eq!(matrix[2][3], 20);
+        assert_eq!(matrix[3][4], 20);
+        assert_eq!(matrix[4][2], 12);
+        assert_eq!(matrix.iter().flatten().sum::<usize>(), 100);
+    }
+
+    #[test]
+    fn test_has_condorcet_cycle() {
+        let matrix = pairwise_matrix(&mut condorcet_cycle_electorate(5), 3, OrdinalEnum::plurality);
+        assert_eq!(condorcet_winner(&matrix), None);
+        assert!(has_condorcet_cycle(&matrix));
+        let matrix = pairwise_matrix(&mut majority_election(), 3, OrdinalEnum::plurality);
+        assert!(!has_condorcet_cycle(&matrix));
+    }
+
+    #[test]
+    fn test_smith_set() {
+        let matrix = pairwise_matrix(&mut majority_election(), 3, OrdinalEnum::plurality);
+        assert_eq!(smith_set(&matrix), vec![CandidateID(2)]);
+        let matrix = pairwise_matrix(&mut condorcet_cycle_electorate(1), 3, OrdinalEnum::plurality);
+        assert_eq!(
+            smith_set(&matrix),
+            vec![CandidateID(0), CandidateID(1), CandidateID(2)]
+        );
+    }
+
+    #[test]
+    fn test_condorcet_winner_fast() {
+        let profiles: Vec<(Vec<HonestVoter>, usize)> = vec![
+            (majority_election(), 3),
+            (runoff_differs(), 3),
+            (irv_differs(), 5),
+            (withdrawal_differs(), 4),
+            (condorcet_cycle_electorate(1), 3),
+        ];
+        for (mut voters, n) in profiles {
+            let matrix = pairwise_matrix(&mut voters, n, OrdinalEnum::plurality);
+            assert_eq!(
+                condorcet_winner_fast(&mut voters, n, OrdinalEnum::plurality),
+                condorcet_winner(&matrix)
+            );
+        }
+    }
+
+    #[test]
+    fn test_kendall_tau_against_utility_ranking() {
+        let ideal = utility_ranking(&majority_election(), 3, higher_index_wins());
+        let plurality = ElectionMethods::plurality(&mut majority_election(), 3, higher_index_wins());
+        assert_eq!(kendall_tau(&ideal, &plurality), 1.0);
+        let mut reversed = ideal.clone();
+        reversed.reverse();
+        assert_eq!(kendall_tau(&ideal, &reversed), -1.0);
+    }
+}
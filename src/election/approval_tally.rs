@@ -0,0 +1,77 @@
+//! Mod to hold the ApprovalTally, which records who approved of each candidate so that approval
+//! methods can count (and reweight) approvals without re-casting ballots.
+
+use crate::election::election_methods::CardinalEnum;
+use crate::election::election_profile::CandidateID;
+use crate::election::voters::Voter;
+use std::collections::HashSet;
+
+/// The approval ballots of an electorate, indexed by candidate: the set of approvers of
+/// CandidateID(i) holds the index of every voter whose ballot approves of them.
+#[derive(Debug, Clone)]
+pub struct ApprovalTally {
+    approvers: Vec<HashSet<usize>>,
+}
+
+impl ApprovalTally {
+    /// Build the tally from the approval ballots the voters cast for the given method
+    pub fn new<T: Voter>(voters: &mut Vec<T>, num_candidates: usize, method: CardinalEnum) -> Self {
+        let mut approvers = vec![HashSet::new(); num_candidates];
+        for (voter, v) in voters.iter_mut().enumerate() {
+            v.cast_approval_ballot(method)
+                .iter()
+                .for_each(|&CandidateID(id)| {
+                    approvers[id].insert(voter);
+                });
+        }
+        Self { approvers }
+    }
+
+    /// The number of voters who approve of candidate
+    pub fn count(&self, CandidateID(id): CandidateID) -> usize {
+        self.approvers[id].len()
+    }
+
+    /// The approval count of every candidate, where counts\[i] is the count of CandidateID(i)
+    pub fn counts(&self) -> Vec<usize> {
+        self.approvers.iter().map(|set| set.len()).collect()
+    }
+
+    /// The indices of the voters who approve of candidate
+    pub fn approvers(&self, CandidateID(id): CandidateID) -> &HashSet<usize> {
+        &self.approvers[id]
+    }
+
+    /// The total weight of the voters who approve of candidate, where weights\[v] is the weight of
+    /// the voter at index v. This lets sequential methods (i.e. proportional approval methods)
+    /// discount voters who are already represented.
+    pub fn reweighted_count(&self, CandidateID(id): CandidateID, weights: &[f64]) -> f64 {
+        self.approvers[id].iter().map(|&voter| weights[voter]).sum()
+    }
+}
+
+/// Unit tests for this module
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::election::election_methods::tests::majority_election;
+    use crate::election::ElectionMethods;
+    use crate::utility_functions::higher_index_wins;
+
+    #[test]
+    fn test_approval_tally() {
+        // Approval ballots are {B, C}, {C} and {B}
+        let tally = ApprovalTally::new(&mut majority_election(), 3, CardinalEnum::approval);
+        let (_, counts) =
+            ElectionMethods::approval_with_counts(&mut majority_election(), 3, higher_index_wins());
+        for id in 0..3 {
+            assert_eq!(tally.count(CandidateID(id)), counts[id]);
+        }
+        assert_eq!(tally.counts(), vec![0, 2, 2]);
+        assert_eq!(tally.approvers(CandidateID(1)), &HashSet::from([0, 2]));
+        assert_eq!(
+            tally.reweighted_count(CandidateID(2), &[0.5, 1.0, 1.0]),
+            1.5
+        );
+    }
+}
@@ -0,0 +1,80 @@
+//! Mod to hold the CandidateRegistry, which maps the names candidates go by in real-world ballot
+//! data to the CandidateIDs the election methods work with.
+
+use crate::election::election_profile::CandidateID;
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt;
+
+/// A two-way mapping between candidate names and CandidateIDs. Candidates are given IDs in the
+/// order they are registered, starting from CandidateID(0), so a registry of n candidates can be
+/// used directly with an election of num_candidates n.
+#[derive(Debug, Clone, Default)]
+pub struct CandidateRegistry {
+    names: Vec<String>,
+    ids: HashMap<String, CandidateID>,
+}
+
+impl CandidateRegistry {
+    /// Create a registry holding no candidates
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a registry of the given names, where names\[i] is given CandidateID(i). Repeated
+    /// names are only registered once.
+    pub fn from_names(names: &[&str]) -> Self {
+        let mut registry = Self::new();
+        names.iter().for_each(|name| {
+            registry.register(name);
+        });
+        registry
+    }
+
+    /// Register a candidate under the given name, returning their CandidateID. If the name is
+    /// already registered, the existing CandidateID is returned.
+    pub fn register(&mut self, name: &str) -> CandidateID {
+        if let Some(&id) = self.ids.get(name) {
+            return id;
+        }
+        let id = CandidateID(self.names.len());
+        self.names.push(name.to_string());
+        self.ids.insert(name.to_string(), id);
+        id
+    }
+
+    /// Look up the CandidateID of the candidate with the given name
+    pub fn id(&self, name: &str) -> Result<CandidateID, UnknownCandidate> {
+        self.ids
+            .get(name)
+            .copied()
+            .ok_or_else(|| UnknownCandidate(name.to_string()))
+    }
+
+    /// Look up the name of the candidate with the given CandidateID, if they are registered
+    pub fn name(&self, CandidateID(id): CandidateID) -> Option<&str> {
+        self.names.get(id).map(|name| name.as_str())
+    }
+
+    /// Get the number of registered candidates
+    pub fn len(&self) -> usize {
+        self.names.len()
+    }
+
+    /// Whether no candidates are registered
+    pub fn is_empty(&self) -> bool {
+        self.names.is_empty()
+    }
+}
+
+/// The error returned when looking up a name which isn't in a CandidateRegistry
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnknownCandidate(pub String);
+
+impl fmt::Display for UnknownCandidate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "no candidate is registered under the name {:?}", self.0)
+    }
+}
+
+impl Error for UnknownCandidate {}
@@ -0,0 +1,50 @@
+//! Mod to hold the error type returned by the checked election methods, for when ballots can't be
+//! trusted to match the election they're counted in (i.e. imported ballot data), or there are too
+//! many of them to count safely.
+
+use crate::election::election_profile::CandidateID;
+use std::error::Error;
+use std::fmt;
+
+/// Describes why a set of ballots can't be counted in an election
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum ElectionError {
+    /// The ballot of the voter at index voter references a candidate which isn't in the election,
+    /// since its index is at least num_candidates.
+    CandidateOutOfRange {
+        voter: usize,
+        candidate: CandidateID,
+        num_candidates: usize,
+    },
+    /// Counting the ballot of the voter at index voter would overflow candidate's total.
+    TallyOverflow {
+        voter: usize,
+        candidate: CandidateID,
+    },
+}
+
+impl fmt::Display for ElectionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ElectionError::CandidateOutOfRange {
+                voter,
+                candidate: CandidateID(id),
+                num_candidates,
+            } => write!(
+                f,
+                "voter {} ranks candidate {}, but the election only has {} candidates",
+                voter, id, num_candidates
+            ),
+            ElectionError::TallyOverflow {
+                voter,
+                candidate: CandidateID(id),
+            } => write!(
+                f,
+                "counting voter {}'s ballot overflows candidate {}'s total",
+                voter, id
+            ),
+        }
+    }
+}
+
+impl Error for ElectionError {}
@@ -2,6 +2,8 @@
 //! various statistics about outcomes.
 
 use crate::election::voters::Voter;
+use crate::utility_functions::generate_candidates;
+use serde::{Deserialize, Serialize};
 use std::cmp::Ordering;
 
 /// Core ElectionProfile struct. Note that instead of the voters vec containing the Voters enum type
@@ -21,6 +23,16 @@ where
 }
 
 impl<T: Voter, F: Fn(&usize, &usize) -> Ordering + Copy> ElectionProfile<T, F> {
+    /// Create a new ElectionProfile from a vec of voters and the number of candidates they are
+    /// choosing between, using the given tie-breaker.
+    pub fn new(voters: Vec<T>, num_candidates: usize, tie_breaker: F) -> Self {
+        Self {
+            voters,
+            candidates: generate_candidates(num_candidates),
+            tie_breaker,
+        }
+    }
+
     /// Get a mut reference to the vec of voters
     pub fn get_voters(&mut self) -> &mut Vec<T> {
         &mut self.voters
@@ -45,8 +57,70 @@ impl<T: Voter, F: Fn(&usize, &usize) -> Ordering + Copy> ElectionProfile<T, F> {
     pub fn get_tie_breaker(&self) -> F {
         self.tie_breaker
     }
+
+    /// Iterate over a summary of each voter (their favorite candidate and the utility they ascribe
+    /// to that candidate) without exposing the voters themselves. Ties for a voter's favorite are
+    /// broken with this ElectionProfile's tie-breaker. This requires utility information, so it
+    /// will panic if the voters are ballot-only (i.e. RealOrdinalVoter or RealCardinalVoter).
+    pub fn voter_summaries(&self) -> impl Iterator<Item = VoterSummary> + '_ {
+        self.voters.iter().map(|voter| {
+            let utilities = voter.utilities();
+            let favorite = (0..utilities.len())
+                .max_by(|&a, &b| {
+                    utilities[a]
+                        .partial_cmp(&utilities[b])
+                        .unwrap()
+                        .then((self.tie_breaker)(&a, &b))
+                })
+                .unwrap();
+            VoterSummary {
+                favorite: CandidateID(favorite),
+                max_utility: utilities[favorite],
+            }
+        })
+    }
+}
+
+/// Summary of a single voter in an ElectionProfile
+#[derive(Debug, PartialEq, Copy, Clone)]
+pub struct VoterSummary {
+    /// The candidate this voter ascribes the most utility to
+    pub favorite: CandidateID,
+    /// The utility this voter ascribes to their favorite candidate
+    pub max_utility: f64,
 }
 
 /// Separate type for indexing candidates
-#[derive(Debug, Eq, PartialEq, Copy, Clone)]
-pub struct CandidateID(pub(crate) usize);
+#[derive(Debug, Eq, PartialEq, Hash, Copy, Clone, Serialize, Deserialize)]
+pub struct CandidateID(pub usize);
+
+/// Unit tests for this module
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::election::election_methods::tests::majority_election;
+    use crate::utility_functions::higher_index_wins;
+
+    #[test]
+    fn test_voter_summaries() {
+        let profile = ElectionProfile::new(majority_election(), 3, higher_index_wins());
+        let summaries = profile.voter_summaries().collect::<Vec<_>>();
+        assert_eq!(
+            summaries,
+            vec![
+                VoterSummary {
+                    favorite: CandidateID(2),
+                    max_utility: 0.6
+                },
+                VoterSummary {
+                    favorite: CandidateID(2),
+                    max_utility: 0.8
+                },
+                VoterSummary {
+                    favorite: CandidateID(1),
+                    max_utility: 0.7
+                },
+            ]
+        );
+    }
+}
@@ -0,0 +1,197 @@
+//! Mod to hold the MethodRegistry, which maps method names to election methods at runtime. Unlike
+//! the generated method enums, the registry can be extended with new methods (i.e. plugins, or
+//! methods defined outside this crate) and dispatched on by name (i.e. from a CLI).
+
+use crate::election::election_methods::ElectionMethods;
+use crate::election::election_profile::CandidateID;
+use crate::election::voters::Voter;
+use std::cmp::Ordering;
+use std::collections::HashMap;
+
+/// An election method with the standard signature, as a trait object
+pub type DynMethod<T, F> = dyn Fn(&mut Vec<T>, usize, F) -> Vec<CandidateID>;
+
+/// A boxed election method with the standard signature
+pub type BoxedMethod<T, F> = Box<DynMethod<T, F>>;
+
+/// Registers each of the listed ElectionMethods under its own name
+macro_rules! register_methods {
+    ($registry:expr, [$($method:ident),* $(,)?]) => {
+        $($registry.register(stringify!($method), ElectionMethods::$method::<T, F>);)*
+    };
+}
+
+/// A registry mapping method names to election methods with the standard signature, for voters of
+/// type T and tie-breakers of type F.
+pub struct MethodRegistry<T: Voter, F: Fn(&usize, &usize) -> Ordering + Copy> {
+    methods: HashMap<String, BoxedMethod<T, F>>,
+}
+
+impl<T: Voter + 'static, F: Fn(&usize, &usize) -> Ordering + Copy + 'static> MethodRegistry<T, F> {
+    /// Create a registry holding every election method with the standard signature, each
+    /// registered under the same name as in the generated method lists.
+    pub fn new() -> Self {
+        let mut registry = Self::empty();
+        register_methods!(
+            registry,
+            [
+                plurality,
+                fptp_runoff,
+                two_round_system,
+                contingent_vote,
+                irv,
+                borda,
+                schulze,
+                tideman_alternative,
+                plurality_smith,
+                coombs_equal_rankings,
+                baldwin,
+                average_rank_elimination,
+                approval,
+                utility_weighted_approval,
+                approval_runoff,
+                score_5,
+                score_10,
+                score_100,
+                score_5_runoff,
+                score_10_runoff,
+                score_100_runoff,
+                star_5,
+                star_10,
+                star_100,
+            ]
+        );
+        registry
+    }
+
+    /// Create a registry holding no methods
+    pub fn empty() -> Self {
+        Self {
+            methods: HashMap::new(),
+        }
+    }
+
+    /// Register method under name, replacing any method already registered under that name.
+    pub fn register<M>(&mut self, name: &str, method: M)
+    where
+        M: Fn(&mut Vec<T>, usize, F) -> Vec<CandidateID> + 'static,
+    {
+        self.methods.insert(name.to_string(), Box::new(method));
+    }
+
+    /// Run the method registered under name, if there is one.
+    pub fn run(
+        &self,
+        name: &str,
+        voters: &mut Vec<T>,
+        num_candidates: usize,
+        tie_breaker: F,
+    ) -> Option<Vec<CandidateID>> {
+        self.methods
+            .get(name)
+            .map(|method| method(voters, num_candidates, tie_breaker))
+    }
+
+    /// Run every registered method on voters, returning each method's name along with its
+    /// ranking, in no particular order. The methods are run one after another on the same voters.
+    pub fn run_all(
+        &self,
+        voters: &mut Vec<T>,
+        num_candidates: usize,
+        tie_breaker: F,
+    ) -> Vec<(&str, Vec<CandidateID>)> {
+        self.methods
+            .iter()
+            .map(|(name, method)| (name.as_str(), method(voters, num_candidates, tie_breaker)))
+            .collect()
+    }
+
+    /// Returns whether a method is registered under name
+    pub fn contains(&self, name: &str) -> bool {
+        self.methods.contains_key(name)
+    }
+
+    /// Returns the names of every registered method, in no particular order
+    pub fn names(&self) -> impl Iterator<Item = &str> + '_ {
+        self.methods.keys().map(|name| name.as_str())
+    }
+}
+
+impl<T: Voter + 'static, F: Fn(&usize, &usize) -> Ordering + Copy + 'static> Default
+    for MethodRegistry<T, F>
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Unit tests for this module
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::election::election_methods::tests::majority_election;
+    use crate::election::HonestVoter;
+    use crate::utility_functions::higher_index_wins;
+
+    #[test]
+    fn test_run_by_name() {
+        let mut registry = MethodRegistry::new();
+        assert_eq!(
+            registry.run(
+                "plurality",
+                &mut majority_election(),
+                3,
+                higher_index_wins()
+            ),
+            Some(ElectionMethods::plurality(
+                &mut majority_election(),
+                3,
+                higher_index_wins()
+            ))
+        );
+        assert_eq!(
+            registry.run(
+                "no_such_method",
+                &mut majority_election(),
+                3,
+                higher_index_wins()
+            ),
+            None
+        );
+
+        // Methods can be registered under any name
+        registry.register(
+            "plurality_reversed",
+            |voters: &mut Vec<HonestVoter>, n, tie_breaker| {
+                let mut ranking = ElectionMethods::plurality(voters, n, tie_breaker);
+                ranking.reverse();
+                ranking
+            },
+        );
+        assert_eq!(
+            registry
+                .run(
+                    "plurality_reversed",
+                    &mut majority_election(),
+                    3,
+                    higher_index_wins()
+                )
+                .unwrap()[2],
+            CandidateID(2)
+        );
+    }
+
+    #[test]
+    fn test_every_method_registered() {
+        let registry = MethodRegistry::<HonestVoter, fn(&usize, &usize) -> Ordering>::new();
+        let methods = ElectionMethods::METHOD_LIST_ordinal
+            .iter()
+            .chain(ElectionMethods::METHOD_LIST_cardinal.iter());
+        let mut count = 0;
+        for name in methods {
+            assert!(registry.contains(name), "{} is not registered", name);
+            count += 1;
+        }
+        assert_eq!(registry.names().count(), count);
+    }
+}
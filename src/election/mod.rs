@@ -1,8 +1,28 @@
+mod analysis;
+mod approval_tally;
+mod candidate_registry;
+mod election_error;
 mod election_profile;
 mod election_methods;
+mod method_registry;
 mod models;
+mod outcome;
+mod simulation;
 mod voters;
 
+pub use approval_tally::ApprovalTally;
+pub use candidate_registry::{CandidateRegistry, UnknownCandidate};
+pub use election_error::ElectionError;
 pub use election_profile::ElectionProfile;
-pub use election_methods::ElectionMethods;
-pub use election_profile::CandidateID;
\ No newline at end of file
+pub use election_methods::{
+    CardinalEnum, ElectionMethods, EqualRankCredit, IrvTieMode, OrdinalEnum, PathStrength,
+    Quota, ScoreAggregation, StarRunoffTie,
+};
+pub use election_profile::CandidateID;
+pub use election_profile::VoterSummary;
+pub use method_registry::{BoxedMethod, DynMethod, MethodRegistry};
+pub use outcome::{evaluate, ElectionOutcome};
+pub use simulation::{simulate, RunningStat, SimulationStats};
+pub use analysis::*;
+pub use models::*;
+pub use voters::*;
\ No newline at end of file
@@ -1 +1,3 @@
-mod spatial_model;
\ No newline at end of file
+mod spatial_model;
+
+pub use spatial_model::*;
\ No newline at end of file
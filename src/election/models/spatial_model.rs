@@ -1,8 +1,35 @@
 //! Mod for the spatial model of voting. Contains generators and related functions dedicated to
 //! building spatial models.
 
-use rand::distributions::Distribution;
+use crate::election::CandidateID;
+use crate::metrics::lp_metric;
+use rand::distributions::{Distribution, Uniform};
 use rand::Rng;
+use rand_distr::Normal;
+use std::io;
+use std::io::Write;
+
+const CLUSTER_WARNING: &str = "Cluster means, standard deviations and counts must all have the \
+    same length, and each cluster's means and standard deviations must have the same dimensions!";
+
+const STD_WARNING: &str = "Cluster standard deviations must be finite and non-negative!";
+
+const BOUNDS_WARNING: &str = "Layout bounds must be finite, with lower no greater than upper!";
+
+const REGION_WARNING: &str = "Every voter location must have one coordinate per split point!";
+
+const REPRESENTATION_WARNING: &str =
+    "Measuring representation requires at least one voter and winner!";
+
+const MEDIAN_WARNING: &str = "Finding the median voter requires at least one voter and candidate!";
+
+/// The locations of the candidates and voters of a spatial election, where
+/// candidate_locations\[i] is the location of CandidateID(i).
+#[derive(Debug, Clone)]
+pub struct SpatialLayout {
+    pub candidate_locations: Vec<Vec<f64>>,
+    pub voter_locations: Vec<Vec<f64>>,
+}
 
 /// Generate a n-dimensional spatial distribution of the specified number of voters and candidates
 /// from the given distributions and rng. Note that for the moment, this generic specification
@@ -63,3 +90,363 @@ fn generate_locations<R: Rng, D: Distribution<f64> + Copy>(
 
     (candidates, voters)
 }
+
+/// Generate a spatial layout in the hypercube [lower, upper]^dimensions, with every coordinate of
+/// every candidate and voter drawn uniformly from [lower, upper]. The candidates are drawn first.
+pub fn generate_bounded_layout<R: Rng>(
+    rng: &mut R,
+    num_candidates: usize,
+    num_voters: usize,
+    dimensions: usize,
+    lower: f64,
+    upper: f64,
+) -> SpatialLayout {
+    if !(lower.is_finite() && upper.is_finite() && lower <= upper) {
+        panic!("{}", BOUNDS_WARNING)
+    }
+    let distributions = vec![vec![Uniform::new_inclusive(lower, upper); dimensions]];
+    let (candidate_locations, voter_locations) = generate_locations(
+        rng,
+        &distributions,
+        &[num_candidates],
+        &distributions,
+        &[num_voters],
+    );
+    SpatialLayout {
+        candidate_locations,
+        voter_locations,
+    }
+}
+
+/// Generate a spatial layout whose voters are drawn from clusters, each with its own diagonal
+/// covariance. cluster_counts\[i] voters are sampled around cluster_means\[i], with the coordinate
+/// in dimension d drawn from a normal distribution with standard deviation cluster_stds\[i]\[d].
+/// Voters are emitted cluster by cluster, in the order the clusters are given. The candidates are
+/// placed at the given candidate_locations. Panics if the cluster arguments differ in length, or if
+/// any cluster has a different number of means than standard deviations.
+pub fn generate_clustered_layout<R: Rng>(
+    rng: &mut R,
+    cluster_means: &[Vec<f64>],
+    cluster_stds: &[Vec<f64>],
+    cluster_counts: &[usize],
+    candidate_locations: Vec<Vec<f64>>,
+) -> SpatialLayout {
+    if cluster_means.len() != cluster_stds.len()
+        || cluster_means.len() != cluster_counts.len()
+        || cluster_means
+            .iter()
+            .zip(cluster_stds)
+            .any(|(means, stds)| means.len() != stds.len())
+    {
+        panic!("{}", CLUSTER_WARNING)
+    }
+
+    let num_voters = cluster_counts.iter().copied().sum();
+    let mut voter_locations = Vec::with_capacity(num_voters);
+    for ((means, stds), &count) in cluster_means.iter().zip(cluster_stds).zip(cluster_counts) {
+        let distributions = means
+            .iter()
+            .zip(stds)
+            .map(|(&mean, &std)| {
+                Normal::new(mean, std).unwrap_or_else(|_| panic!("{}", STD_WARNING))
+            })
+            .collect::<Vec<_>>();
+        for _ in 0..count {
+            voter_locations.push(distributions.iter().map(|&d| rng.sample(d)).collect());
+        }
+    }
+
+    SpatialLayout {
+        candidate_locations,
+        voter_locations,
+    }
+}
+
+/// Measure how well a set of elected candidates covers the voter distribution, for evaluating
+/// multi-winner (e.g. proportional representation) methods spatially. The score is the average
+/// distance, as measured by the given metric, from each voter to the nearest elected candidate.
+/// Lower is better. Panics if there are no winners or no voters.
+pub fn representation_score<Metric: Fn(&Vec<f64>, &Vec<f64>) -> f64>(
+    winners: &[CandidateID],
+    candidate_locations: &Vec<Vec<f64>>,
+    voter_locations: &Vec<Vec<f64>>,
+    metric: Metric,
+) -> f64 {
+    if winners.is_empty() || voter_locations.is_empty() {
+        panic!("{}", REPRESENTATION_WARNING);
+    }
+    let total = voter_locations
+        .iter()
+        .map(|voter_location| {
+            winners
+                .iter()
+                .map(|&CandidateID(id)| metric(&candidate_locations[id], voter_location))
+                .fold(f64::INFINITY, f64::min)
+        })
+        .sum::<f64>();
+    total / voter_locations.len() as f64
+}
+
+/// Split the voters into the hyperrectangular regions formed by cutting each dimension d at
+/// splits\[d], for analyzing subgroups of an electorate (i.e. quadrants in 2D). Returns the indices
+/// of the voters in each of the 2^d regions, where a voter belongs to region r if, for every
+/// dimension d, bit d of r is set exactly when their coordinate in dimension d is at least
+/// splits\[d]. Regions with no voters are empty. Panics if any voter location doesn't have as many
+/// dimensions as there are split points.
+pub fn partition_by_region(voter_locations: &Vec<Vec<f64>>, splits: &[f64]) -> Vec<Vec<usize>> {
+    let mut regions = vec![Vec::new(); 1 << splits.len()];
+    for (voter, location) in voter_locations.iter().enumerate() {
+        if location.len() != splits.len() {
+            panic!("{}", REGION_WARNING)
+        }
+        let region = location
+            .iter()
+            .zip(splits)
+            .enumerate()
+            .filter(|(_, (x, split))| x >= split)
+            .fold(0, |region, (d, _)| region | 1 << d);
+        regions[region].push(voter);
+    }
+    regions
+}
+
+/// Find the candidate closest (by Euclidean distance) to the median voter, taken as the point whose
+/// coordinate in each dimension is the median of the voters' coordinates in that dimension (the
+/// mean of the middle two for an even number of voters). In 1D this is the candidate the median
+/// voter theorem predicts should win under a Condorcet-consistent method. Ties in distance go to
+/// the lower CandidateID. Panics if there are no voters or no candidates.
+pub fn median_voter_candidate(
+    candidate_locations: &Vec<Vec<f64>>,
+    voter_locations: &Vec<Vec<f64>>,
+) -> CandidateID {
+    if candidate_locations.is_empty() || voter_locations.is_empty() {
+        panic!("{}", MEDIAN_WARNING);
+    }
+    let median = (0..voter_locations[0].len())
+        .map(|d| {
+            let mut coordinates = voter_locations
+                .iter()
+                .map(|location| location[d])
+                .collect::<Vec<_>>();
+            coordinates.sort_by(|a, b| a.partial_cmp(b).unwrap());
+            let middle = coordinates.len() / 2;
+            if coordinates.len() % 2 == 0 {
+                (coordinates[middle - 1] + coordinates[middle]) / 2f64
+            } else {
+                coordinates[middle]
+            }
+        })
+        .collect::<Vec<_>>();
+    let distances = candidate_locations
+        .iter()
+        .map(|location| lp_metric::<2>(location, &median))
+        .collect::<Vec<_>>();
+    let closest = (0..distances.len())
+        .min_by(|&a, &b| distances[a].partial_cmp(&distances[b]).unwrap())
+        .unwrap();
+    CandidateID(closest)
+}
+
+/// Write the locations of a 2D spatial election to writer as CSV for plotting, with the header
+/// type,x,y,is_winner. One row is written per candidate (type candidate, in CandidateID order)
+/// followed by one row per voter (type voter); is_winner is true only on the winner's row.
+/// Returns an InvalidInput error, without writing anything, if any location is not 2D.
+pub fn write_spatial_csv(
+    candidate_locations: &[Vec<f64>],
+    voter_locations: &[Vec<f64>],
+    winner: CandidateID,
+    mut writer: impl Write,
+) -> io::Result<()> {
+    if let Some(location) = candidate_locations
+        .iter()
+        .chain(voter_locations)
+        .find(|location| location.len() != 2)
+    {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!(
+                "only 2D layouts can be exported, found a {}D location",
+                location.len()
+            ),
+        ));
+    }
+
+    writeln!(writer, "type,x,y,is_winner")?;
+    for (id, location) in candidate_locations.iter().enumerate() {
+        let is_winner = id == winner.0;
+        writeln!(
+            writer,
+            "candidate,{},{},{}",
+            location[0], location[1], is_winner
+        )?;
+    }
+    for location in voter_locations {
+        writeln!(writer, "voter,{},{},false", location[0], location[1])?;
+    }
+    Ok(())
+}
+
+/// Unit tests for this module
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::election::analysis::condorcet_winner_fast;
+    use crate::election::{ApprovalThresholdBehavior, HonestVoter, OrdinalEnum};
+    use crate::utility_generators::distance_utilities;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    #[test]
+    fn test_generate_bounded_layout() {
+        let layout = generate_bounded_layout(&mut StdRng::seed_from_u64(718), 5, 200, 3, -2.0, 0.5);
+        assert_eq!(layout.candidate_locations.len(), 5);
+        assert_eq!(layout.voter_locations.len(), 200);
+        for location in layout
+            .candidate_locations
+            .iter()
+            .chain(&layout.voter_locations)
+        {
+            assert_eq!(location.len(), 3);
+            assert!(location.iter().all(|x| (-2.0..=0.5).contains(x)));
+        }
+    }
+
+    #[test]
+    fn test_generate_clustered_layout() {
+        let means = vec![vec![-10.0, 0.0], vec![10.0, 5.0]];
+        let stds = vec![vec![0.5, 0.1], vec![1.0, 0.2]];
+        let counts = [200, 300];
+        let layout = generate_clustered_layout(
+            &mut StdRng::seed_from_u64(665),
+            &means,
+            &stds,
+            &counts,
+            vec![vec![0.0, 0.0]],
+        );
+        assert_eq!(layout.voter_locations.len(), 500);
+        assert_eq!(layout.candidate_locations, vec![vec![0.0, 0.0]]);
+
+        let (first, second) = layout.voter_locations.split_at(counts[0]);
+        for (cluster, mean, std) in [(first, &means[0], &stds[0]), (second, &means[1], &stds[1])] {
+            for d in 0..2 {
+                let sample_mean =
+                    cluster.iter().map(|location| location[d]).sum::<f64>() / cluster.len() as f64;
+                assert!((sample_mean - mean[d]).abs() < std[d] / 2.0);
+                // No point should stray more than 5 standard deviations from its cluster's mean
+                assert!(cluster
+                    .iter()
+                    .all(|location| (location[d] - mean[d]).abs() < 5.0 * std[d]));
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "same dimensions")]
+    fn test_generate_clustered_layout_mismatched_dimensions() {
+        generate_clustered_layout(
+            &mut StdRng::seed_from_u64(665),
+            &[vec![0.0, 0.0]],
+            &[vec![1.0]],
+            &[10],
+            vec![vec![0.0, 0.0]],
+        );
+    }
+
+    #[test]
+    fn test_representation_score() {
+        let candidate_locations = vec![vec![0.0], vec![1.0], vec![10.0]];
+        let voter_locations = vec![vec![0.0], vec![0.5], vec![9.5], vec![10.0]];
+        let spread = representation_score(
+            &[CandidateID(0), CandidateID(2)],
+            &candidate_locations,
+            &voter_locations,
+            lp_metric::<2>,
+        );
+        let clustered = representation_score(
+            &[CandidateID(0), CandidateID(1)],
+            &candidate_locations,
+            &voter_locations,
+            lp_metric::<2>,
+        );
+        assert!((spread - 0.25).abs() < 1e-12);
+        assert!(spread < clustered);
+    }
+
+    #[test]
+    #[should_panic(expected = "at least one voter and winner")]
+    fn test_representation_score_no_winners() {
+        representation_score(&[], &vec![vec![0.0]], &vec![vec![0.0]], lp_metric::<2>);
+    }
+
+    #[test]
+    #[should_panic(expected = "at least one voter and winner")]
+    fn test_representation_score_no_voters() {
+        representation_score(&[CandidateID(0)], &vec![vec![0.0]], &Vec::new(), lp_metric::<2>);
+    }
+
+    #[test]
+    fn test_write_spatial_csv() {
+        let candidate_locations = vec![vec![0.0, 0.0], vec![1.0, 0.5], vec![-1.0, 2.0]];
+        let voter_locations = vec![vec![0.25, 0.25], vec![0.75, -0.5]];
+        let mut file = Vec::new();
+        write_spatial_csv(
+            &candidate_locations,
+            &voter_locations,
+            CandidateID(1),
+            &mut file,
+        )
+        .unwrap();
+        assert_eq!(
+            String::from_utf8(file).unwrap(),
+            "type,x,y,is_winner\n\
+             candidate,0,0,false\n\
+             candidate,1,0.5,true\n\
+             candidate,-1,2,false\n\
+             voter,0.25,0.25,false\n\
+             voter,0.75,-0.5,false\n"
+        );
+
+        let error =
+            write_spatial_csv(&[vec![0.0, 0.0, 0.0]], &[], CandidateID(0), Vec::new()).unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::InvalidInput);
+    }
+
+    #[test]
+    fn test_partition_by_region() {
+        let voter_locations = vec![vec![-1.0], vec![0.5], vec![0.2], vec![-0.3], vec![2.0]];
+        assert_eq!(
+            partition_by_region(&voter_locations, &[0.2]),
+            vec![vec![0, 3], vec![1, 2, 4]]
+        );
+
+        let voter_locations = vec![vec![-1.0, 1.0], vec![1.0, -1.0], vec![1.0, 1.0]];
+        assert_eq!(
+            partition_by_region(&voter_locations, &[0.0, 0.0]),
+            vec![vec![], vec![1], vec![0], vec![2]]
+        );
+    }
+
+    #[test]
+    fn test_median_voter_candidate() {
+        // The median voter sits at 0.4, nearest to B, even though C is nearer the mean of 1.36
+        let candidate_locations = vec![vec![-1.0], vec![0.5], vec![1.5]];
+        let voter_locations = vec![vec![-0.8], vec![0.1], vec![0.4], vec![1.2], vec![5.9]];
+        let median_candidate = median_voter_candidate(&candidate_locations, &voter_locations);
+        assert_eq!(median_candidate, CandidateID(1));
+
+        let mut voters = voter_locations
+            .iter()
+            .map(|location| {
+                HonestVoter::new(
+                    distance_utilities(&candidate_locations, location, lp_metric::<2>),
+                    true,
+                    ApprovalThresholdBehavior::Mean,
+                )
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            condorcet_winner_fast(&mut voters, 3, OrdinalEnum::plurality),
+            Some(median_candidate)
+        );
+    }
+}
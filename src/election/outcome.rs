@@ -0,0 +1,107 @@
+//! Mod for the ElectionOutcome, which gathers everything worth knowing about a single run of an
+//! election method into one serializable record, for analysis pipelines outside of this crate.
+
+use crate::election::analysis::{
+    condorcet_winner_fast, utility_ranking, voter_satisfaction_efficiency,
+};
+use crate::election::election_methods::OrdinalEnum;
+use crate::election::election_profile::CandidateID;
+use crate::election::voters::Voter;
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+
+/// The result of running one election method on one electorate, as produced by evaluate
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ElectionOutcome {
+    /// The name of the method which was run
+    pub method: String,
+    /// The full ranking the method produced, winner first
+    pub ranking: Vec<CandidateID>,
+    /// The total utility of each candidate across all voters, where utility_totals\[i] is the
+    /// total of CandidateID(i)
+    pub utility_totals: Vec<f64>,
+    /// The voter satisfaction efficiency of the winner
+    pub vse: f64,
+    /// The Condorcet winner, if there is one
+    pub condorcet_winner: Option<CandidateID>,
+    /// Whether the method elected the Condorcet winner (false if there is none)
+    pub elected_condorcet_winner: bool,
+    /// Whether the method elected the candidate with the greatest total utility, with ties for
+    /// that candidate broken by the tie-breaker
+    pub elected_utility_winner: bool,
+}
+
+/// Run the given method on voters and record the outcome under method_name. The Condorcet winner
+/// is found from honest plurality ordinal ballots. Requires that the voters contain utility
+/// information.
+pub fn evaluate<T, F, M>(
+    voters: &mut Vec<T>,
+    num_candidates: usize,
+    tie_breaker: F,
+    method_name: &str,
+    method: M,
+) -> ElectionOutcome
+where
+    T: Voter,
+    F: Fn(&usize, &usize) -> Ordering + Copy,
+    M: Fn(&mut Vec<T>, usize, F) -> Vec<CandidateID>,
+{
+    let ranking = method(voters, num_candidates, tie_breaker);
+    let winner = ranking[0];
+    let mut utility_totals = vec![0f64; num_candidates];
+    for voter in voters.iter() {
+        voter
+            .utilities()
+            .iter()
+            .zip(utility_totals.iter_mut())
+            .for_each(|(&utility, total)| *total += utility);
+    }
+    let condorcet_winner = condorcet_winner_fast(voters, num_candidates, OrdinalEnum::plurality);
+    ElectionOutcome {
+        method: method_name.to_string(),
+        vse: voter_satisfaction_efficiency(voters, winner),
+        elected_condorcet_winner: condorcet_winner == Some(winner),
+        elected_utility_winner: utility_ranking(voters, num_candidates, tie_breaker)[0] == winner,
+        condorcet_winner,
+        utility_totals,
+        ranking,
+    }
+}
+
+/// Unit tests for this module
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::election::election_methods::tests::majority_election;
+    use crate::election::ElectionMethods;
+    use crate::utility_functions::higher_index_wins;
+
+    #[test]
+    fn test_evaluate() {
+        let outcome = evaluate(
+            &mut majority_election(),
+            3,
+            higher_index_wins(),
+            "plurality",
+            ElectionMethods::plurality,
+        );
+        assert_eq!(outcome.method, "plurality");
+        assert_eq!(
+            outcome.ranking,
+            vec![CandidateID(2), CandidateID(1), CandidateID(0)]
+        );
+        let expected = [0.9, 1.5, 1.6];
+        assert_eq!(outcome.utility_totals.len(), 3);
+        for (total, expected) in outcome.utility_totals.iter().zip(expected) {
+            assert!((total - expected).abs() < 1e-9);
+        }
+        assert!((outcome.vse - 1.0).abs() < 1e-9);
+        assert_eq!(outcome.condorcet_winner, Some(CandidateID(2)));
+        assert!(outcome.elected_condorcet_winner);
+        assert!(outcome.elected_utility_winner);
+
+        let json = serde_json::to_value(&outcome).unwrap();
+        assert_eq!(json.as_object().unwrap().len(), 7);
+        assert_eq!(json["condorcet_winner"], serde_json::json!(2));
+    }
+}
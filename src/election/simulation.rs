@@ -0,0 +1,176 @@
+//! Mod for running repeated simulated elections and summarizing how well a method performs across
+//! them, with enough dispersion information to judge whether differences between methods are
+//! significant.
+
+use crate::election::analysis::{condorcet_winner_fast, voter_satisfaction_efficiency};
+use crate::election::election_methods::OrdinalEnum;
+use crate::election::election_profile::CandidateID;
+use crate::election::voters::{ApprovalThresholdBehavior, HonestVoter};
+use crate::utility_generators::UtilityModel;
+use rand::Rng;
+use std::cmp::Ordering;
+
+/// The z-score of a two-sided 95% confidence interval under the normal approximation
+const Z_95: f64 = 1.96;
+
+/// Running totals of a sample, from which its mean and the standard error of the mean are found
+/// without keeping every observation.
+#[derive(Debug, Copy, Clone, Default)]
+pub struct RunningStat {
+    count: usize,
+    sum: f64,
+    sum_of_squares: f64,
+}
+
+impl RunningStat {
+    /// Add an observation to the sample
+    pub fn push(&mut self, x: f64) {
+        self.count += 1;
+        self.sum += x;
+        self.sum_of_squares += x * x;
+    }
+
+    /// The number of observations
+    pub fn count(&self) -> usize {
+        self.count
+    }
+
+    /// The sample mean, or NaN if there are no observations
+    pub fn mean(&self) -> f64 {
+        self.sum / self.count as f64
+    }
+
+    /// The standard error of the sample mean, from the (Bessel-corrected) sample variance. This is
+    /// NaN if there are fewer than two observations.
+    pub fn standard_error(&self) -> f64 {
+        if self.count < 2 {
+            return f64::NAN;
+        }
+        let n = self.count as f64;
+        let variance = ((self.sum_of_squares - self.sum * self.sum / n) / (n - 1f64)).max(0f64);
+        (variance / n).sqrt()
+    }
+
+    /// The 95% confidence interval (lower, upper) for the mean, under the normal approximation
+    pub fn confidence_interval(&self) -> (f64, f64) {
+        let margin = Z_95 * self.standard_error();
+        (self.mean() - margin, self.mean() + margin)
+    }
+}
+
+/// The results of simulate: the voter satisfaction efficiency of the winner in every trial, and
+/// whether the winner was the Condorcet winner in every trial that had one.
+#[derive(Debug, Copy, Clone, Default)]
+pub struct SimulationStats {
+    pub vse: RunningStat,
+    pub condorcet_efficiency: RunningStat,
+}
+
+impl SimulationStats {
+    /// The number of trials run
+    pub fn trials(&self) -> usize {
+        self.vse.count()
+    }
+
+    /// The mean voter satisfaction efficiency across trials
+    pub fn mean_vse(&self) -> f64 {
+        self.vse.mean()
+    }
+
+    /// The fraction of trials with a Condorcet winner in which the Condorcet winner won
+    pub fn mean_condorcet_efficiency(&self) -> f64 {
+        self.condorcet_efficiency.mean()
+    }
+}
+
+/// Run trials elections of num_voters honest voters (unscaled, with the Mean approval threshold)
+/// whose utilities are drawn from model, electing a winner by the given method each time, and
+/// summarize how well the method did.
+pub fn simulate<R, U, F, M>(
+    rng: &mut R,
+    trials: usize,
+    num_voters: usize,
+    num_candidates: usize,
+    model: &U,
+    tie_breaker: F,
+    method: M,
+) -> SimulationStats
+where
+    R: Rng,
+    U: UtilityModel,
+    F: Fn(&usize, &usize) -> Ordering + Copy,
+    M: Fn(&mut Vec<HonestVoter>, usize, F) -> Vec<CandidateID>,
+{
+    let mut stats = SimulationStats::default();
+    for _ in 0..trials {
+        let mut voters = (0..num_voters)
+            .map(|_| {
+                HonestVoter::new(
+                    model.generate(rng, num_candidates),
+                    false,
+                    ApprovalThresholdBehavior::Mean,
+                )
+            })
+            .collect::<Vec<_>>();
+        let winner = method(&mut voters, num_candidates, tie_breaker)[0];
+        stats
+            .vse
+            .push(voter_satisfaction_efficiency(&voters, winner));
+        if let Some(condorcet_winner) =
+            condorcet_winner_fast(&mut voters, num_candidates, OrdinalEnum::plurality)
+        {
+            let elected = if winner == condorcet_winner {
+                1f64
+            } else {
+                0f64
+            };
+            stats.condorcet_efficiency.push(elected);
+        }
+    }
+    stats
+}
+
+/// Unit tests for this module
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::election::ElectionMethods;
+    use crate::utility_functions::higher_index_wins;
+    use crate::utility_generators::UniformModel;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    #[test]
+    fn test_simulation_confidence_intervals() {
+        let mut rng = StdRng::seed_from_u64(706);
+        let mut run = |trials| {
+            simulate(
+                &mut rng,
+                trials,
+                15,
+                4,
+                &UniformModel,
+                higher_index_wins(),
+                ElectionMethods::plurality,
+            )
+        };
+        let (few, many) = (run(20), run(500));
+        assert_eq!(many.trials(), 500);
+
+        let width = |(lower, upper): (f64, f64)| upper - lower;
+        for (stat, mean) in [
+            (few.vse, few.mean_vse()),
+            (many.vse, many.mean_vse()),
+            (few.condorcet_efficiency, few.mean_condorcet_efficiency()),
+            (many.condorcet_efficiency, many.mean_condorcet_efficiency()),
+        ] {
+            let (lower, upper) = stat.confidence_interval();
+            assert!(lower <= mean && mean <= upper);
+        }
+        assert!(width(many.vse.confidence_interval()) < width(few.vse.confidence_interval()));
+        assert!(
+            width(many.condorcet_efficiency.confidence_interval())
+                < width(few.condorcet_efficiency.confidence_interval())
+        );
+    }
+}
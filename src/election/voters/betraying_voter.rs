@@ -0,0 +1,202 @@
+//! This mod contains the implementation for the BetrayingVoter struct, which wraps another voter
+//! and can betray that voter's favorite candidate on every ballot it casts.
+
+use crate::election::election_profile::CandidateID;
+use crate::election::voters::*;
+use std::cmp::Ordering;
+
+use crate::election::election_methods::CardinalEnum;
+use crate::election::election_methods::OrdinalEnum;
+
+/// A BetrayingVoter casts the ballots of the voter it wraps. While betraying, it instead betrays
+/// that voter's favorite (the top of their ordinal ballot as cast for plurality): the favorite is
+/// ranked last on ordinal ballots, scored 0 on cardinal ballots, and left off of approval
+/// ballots, in which case the next favorite is approved if nothing else would be. Honest
+/// preferences and utilities are always the wrapped voter's, so that an electorate can mix
+/// betraying and sincere voters and still be judged by what they really want.
+#[derive(Clone)]
+pub struct BetrayingVoter<T: Voter> {
+    /// The voter whose ballots are cast
+    voter: T,
+
+    /// Whether the wrapped voter's favorite is betrayed
+    betraying: bool,
+
+    /// Cache of the last betrayed ordinal ballot
+    ordinal_ballot: Vec<CandidateID>,
+
+    /// Cache of the last betrayed ordinal-equal ballot
+    ordinal_equal_ballot: Vec<Vec<CandidateID>>,
+
+    /// Cache of the last betrayed cardinal ballot
+    cardinal_ballot: Vec<usize>,
+
+    /// Cache of the last betrayed approval ballot
+    approval_ballot: Vec<CandidateID>,
+}
+
+impl<T: Voter> BetrayingVoter<T> {
+    /// Wrap voter, betraying their favorite if betraying is set
+    pub fn new(voter: T, betraying: bool) -> Self {
+        Self {
+            voter,
+            betraying,
+            ordinal_ballot: Vec::new(),
+            ordinal_equal_ballot: Vec::new(),
+            cardinal_ballot: Vec::new(),
+            approval_ballot: Vec::new(),
+        }
+    }
+
+    /// Whether the wrapped voter's favorite is betrayed
+    pub fn is_betraying(&self) -> bool {
+        self.betraying
+    }
+
+    /// The wrapped voter
+    pub fn voter(&self) -> &T {
+        &self.voter
+    }
+
+    /// The wrapped voter's sincere favorite and next favorite, if there are any
+    fn favorites(&mut self) -> (Option<CandidateID>, Option<CandidateID>) {
+        let ballot = self.voter.cast_ordinal_ballot(OrdinalEnum::plurality);
+        (ballot.first().copied(), ballot.get(1).copied())
+    }
+}
+
+impl<T: Voter> Voter for BetrayingVoter<T> {
+    /// The favorite is moved to the bottom of the wrapped voter's ordinal ballot
+    fn cast_ordinal_ballot(&mut self, method: OrdinalEnum) -> &Vec<CandidateID> {
+        if !self.betraying {
+            return self.voter.cast_ordinal_ballot(method);
+        }
+        let (favorite, _) = self.favorites();
+        let mut ballot = self.voter.cast_ordinal_ballot(method).clone();
+        if let Some(index) = ballot.iter().position(|&c| Some(c) == favorite) {
+            let betrayed = ballot.remove(index);
+            ballot.push(betrayed);
+        }
+        self.ordinal_ballot = ballot;
+        &self.ordinal_ballot
+    }
+
+    /// The favorite is moved out of their rank into a rank of their own at the bottom
+    fn cast_ordinal_equal_ballot(&mut self, method_name: &str) -> &Vec<Vec<CandidateID>> {
+        if !self.betraying {
+            return self.voter.cast_ordinal_equal_ballot(method_name);
+        }
+        let (favorite, _) = self.favorites();
+        let mut ballot = self.voter.cast_ordinal_equal_ballot(method_name).clone();
+        if let Some(favorite) = favorite {
+            ballot
+                .iter_mut()
+                .for_each(|rank| rank.retain(|&c| c != favorite));
+            ballot.retain(|rank| !rank.is_empty());
+            ballot.push(vec![favorite]);
+        }
+        self.ordinal_equal_ballot = ballot;
+        &self.ordinal_equal_ballot
+    }
+
+    /// The favorite is given the lowest score
+    fn cast_cardinal_ballot(&mut self, range: usize, method: CardinalEnum) -> &Vec<usize> {
+        if !self.betraying {
+            return self.voter.cast_cardinal_ballot(range, method);
+        }
+        let (favorite, _) = self.favorites();
+        let mut ballot = self.voter.cast_cardinal_ballot(range, method).clone();
+        if let Some(CandidateID(favorite)) = favorite {
+            ballot[favorite] = 0;
+        }
+        self.cardinal_ballot = ballot;
+        &self.cardinal_ballot
+    }
+
+    /// The favorite is left off, and the next favorite approved if that would leave no approvals
+    fn cast_approval_ballot(&mut self, method: CardinalEnum) -> &Vec<CandidateID> {
+        if !self.betraying {
+            return self.voter.cast_approval_ballot(method);
+        }
+        let (favorite, next_favorite) = self.favorites();
+        let mut ballot = self.voter.cast_approval_ballot(method).clone();
+        ballot.retain(|&c| Some(c) != favorite);
+        if ballot.is_empty() {
+            ballot.extend(next_favorite);
+        }
+        self.approval_ballot = ballot;
+        &self.approval_ballot
+    }
+
+    fn honest_preference(&self, first: CandidateID, second: CandidateID) -> Ordering {
+        self.voter.honest_preference(first, second)
+    }
+
+    fn utilities(&self) -> &Vec<f64> {
+        self.voter.utilities()
+    }
+
+    fn candidate_utility(&self, candidate: CandidateID) -> f64 {
+        self.voter.candidate_utility(candidate)
+    }
+
+    fn num_candidates(&self) -> usize {
+        self.voter.num_candidates()
+    }
+
+    fn may_be_truncated(&self) -> bool {
+        self.voter.may_be_truncated()
+    }
+
+    fn remove_candidate(&mut self, candidate: CandidateID) {
+        self.voter.remove_candidate(candidate);
+    }
+
+    fn redraw_ballots(&mut self) {
+        self.voter.redraw_ballots();
+    }
+}
+
+/// Unit tests for this module
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::election::voters::ApprovalThresholdBehavior::Mean;
+
+    #[test]
+    fn favorite_betrayed_on_each_ballot() {
+        let honest = HonestVoter::new(vec![1.0, 0.2, 0.5], false, Mean);
+        let mut sincere = BetrayingVoter::new(honest.clone(), false);
+        assert_eq!(
+            sincere.cast_ordinal_ballot(OrdinalEnum::irv),
+            &vec![CandidateID(0), CandidateID(2), CandidateID(1)]
+        );
+
+        let mut voter = BetrayingVoter::new(honest, true);
+        assert_eq!(
+            voter.cast_ordinal_ballot(OrdinalEnum::irv),
+            &vec![CandidateID(2), CandidateID(1), CandidateID(0)]
+        );
+        assert_eq!(
+            voter.cast_ordinal_equal_ballot("bucklin"),
+            &vec![
+                vec![CandidateID(2)],
+                vec![CandidateID(1)],
+                vec![CandidateID(0)]
+            ]
+        );
+        assert_eq!(
+            voter.cast_cardinal_ballot(10, CardinalEnum::score_10),
+            &vec![0, 2, 5]
+        );
+        // Only the favorite clears the mean, so the next favorite is approved instead
+        assert_eq!(
+            voter.cast_approval_ballot(CardinalEnum::approval),
+            &vec![CandidateID(2)]
+        );
+        assert_eq!(
+            voter.honest_preference(CandidateID(0), CandidateID(2)),
+            Ordering::Greater
+        );
+    }
+}
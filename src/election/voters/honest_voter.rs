@@ -4,6 +4,7 @@
 use crate::election::election_profile::CandidateID;
 use crate::election::voters::*;
 use crate::utility_functions::*;
+use rand::Rng;
 use std::cmp::Ordering;
 use std::collections::HashMap;
 
@@ -12,6 +13,14 @@ use crate::election::election_methods::OrdinalEnum;
 
 /// An HonestVoter represents a voter who casts their ballot directly off of their utility
 /// assessment of the candidates; that is, non-strategically.
+///
+/// A voter whose utilities are all equal (see is_indifferent) has no preferences to express, so
+/// their ballots are deliberately uniform, and never change the relative standing of candidates:
+/// their ordinal-equal ballot ranks every candidate in a single tier; their ordinal ballot, which
+/// can't express ties, is empty, so they abstain; a scaling voter rates every candidate at the
+/// midpoint of the range, while a non-scaling voter rates every candidate off of the shared
+/// utility as usual; and with the Mean threshold, they approve of everyone.
+#[derive(Clone)]
 pub struct HonestVoter {
     /// A vector containing this voter's assessment of the utility the candidates provide them
     /// as a float in the range [0, 1].
@@ -29,6 +38,9 @@ pub struct HonestVoter {
     /// This voter's ApprovalThresholdBehavior
     threshold_behavior: ApprovalThresholdBehavior,
 
+    /// How this voter rounds their utilities into ratings when casting a cardinal ballot.
+    rounding: RoundingMode,
+
     /// Since an HonestVoter always votes honestly, their approval ballot should never change.
     cached_approval_ballot: Vec<CandidateID>,
 
@@ -90,32 +102,75 @@ impl HonestVoter {
             ApprovalThresholdBehavior::Preset(bound) => {
                 generate_approval_ballot(&utilities, *bound)
             }
+            ApprovalThresholdBehavior::TopFraction(fraction) => {
+                // Always approve at least the favorite
+                let count = (fraction * n as f64).ceil().max(1f64) as usize;
+                candidates.iter().copied().take(count).collect()
+            }
+        };
+
+        let mut voter = Self {
+            utilities,
+            scales,
+            threshold_behavior,
+            rounding: RoundingMode::default(),
+            cached_approval_ballot,
+            cached_ordinal_vote: candidates,
+            cached_ordinal_equal_vote: candidates_with_equality,
+            cached_scaled_utilities: None,
+            cached_cardinal_ballots: HashMap::new(),
         };
 
+        // An indifferent voter can't rank the candidates without implying preferences, so they
+        // abstain on ordinal ballots; with no spread to scale, they sit at the midpoint instead
+        let indifferent = voter.is_indifferent();
+        if indifferent {
+            voter.cached_ordinal_vote.clear();
+        }
         if scales {
-            let scaled_utilities = scale_utilities_linearly(&utilities);
-            Self {
-                utilities,
-                scales,
-                threshold_behavior,
-                cached_approval_ballot,
-                cached_ordinal_vote: candidates,
-                cached_ordinal_equal_vote: candidates_with_equality,
-                cached_scaled_utilities: Some(scaled_utilities),
-                cached_cardinal_ballots: HashMap::new(),
-            }
-        } else {
-            Self {
-                utilities,
-                scales,
-                threshold_behavior,
-                cached_approval_ballot,
-                cached_ordinal_vote: candidates,
-                cached_ordinal_equal_vote: candidates_with_equality,
-                cached_scaled_utilities: None,
-                cached_cardinal_ballots: HashMap::new(),
-            }
+            voter.cached_scaled_utilities = Some(if indifferent {
+                vec![0.5; n]
+            } else {
+                scale_utilities_linearly(&voter.utilities)
+            });
         }
+        voter
+    }
+
+    /// Returns whether this voter's utilities are all equal, so that they are indifferent between
+    /// every candidate
+    pub fn is_indifferent(&self) -> bool {
+        self.utilities.iter().all(|&u| u == self.utilities[0])
+    }
+
+    /// Set how this voter rounds their utilities into ratings when casting cardinal ballots.
+    /// Any cardinal ballots already cached are discarded.
+    pub fn with_rounding(mut self, rounding: RoundingMode) -> Self {
+        self.rounding = rounding;
+        self.cached_cardinal_ballots.clear();
+        self
+    }
+
+    /// Returns a copy of this voter with the given utilities in place of their own. Everything
+    /// else about the voter (scaling, approval threshold behavior and rounding) is unchanged.
+    pub fn with_utilities(&self, utilities: Vec<f64>) -> Self {
+        HonestVoter::new(utilities, self.scales, self.threshold_behavior.clone())
+            .with_rounding(self.rounding.clone())
+    }
+
+    /// Replace this voter's utilities in place, recomputing every cached ballot from the new
+    /// utilities and discarding any cached cardinal ballots. Everything else about the voter is
+    /// unchanged, as for with_utilities.
+    pub fn set_utilities(&mut self, new: Vec<f64>) {
+        *self = self.with_utilities(new);
+    }
+
+    /// Warm the cardinal ballot cache for each of the given ranges, so that later calls to
+    /// cast_cardinal_ballot with those ranges don't need to compute the ballot.
+    pub fn precompute_cardinal_ballots(&mut self, ranges: &[usize]) {
+        ranges
+            .iter()
+            .for_each(|&range| self.calculate_cardinal_ballot(range));
     }
 
     fn calculate_cardinal_ballot(&mut self, range: usize) {
@@ -133,9 +188,25 @@ impl HonestVoter {
         };
 
         // Convert f64 utilities to usize ratings in range [0, range]
+        let rounding = &mut self.rounding;
         let ballot = adjusted_utilities
             .into_iter()
-            .map(|&f| (range as f64 * f).round() as usize)
+            .map(|&f| {
+                let rating = range as f64 * f;
+                let rounded = match rounding {
+                    RoundingMode::Round => rating.round(),
+                    RoundingMode::Floor => rating.floor(),
+                    RoundingMode::Ceil => rating.ceil(),
+                    RoundingMode::Stochastic(rng) => {
+                        if rng.gen::<f64>() < rating - rating.floor() {
+                            rating.ceil()
+                        } else {
+                            rating.floor()
+                        }
+                    }
+                };
+                rounded as usize
+            })
             .collect();
 
         // Cache the ballot vec
@@ -186,13 +257,24 @@ impl Voter for HonestVoter {
     fn candidate_utility(&self, CandidateID(id): CandidateID) -> f64 {
         self.utilities[id]
     }
+
+    fn num_candidates(&self) -> usize {
+        self.utilities.len()
+    }
+
+    fn remove_candidate(&mut self, CandidateID(id): CandidateID) {
+        let mut utilities = self.utilities.clone();
+        utilities.remove(id);
+        *self = self.with_utilities(utilities);
+    }
 }
 
 /// Unit tests for this module
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::election::voters::ApprovalThresholdBehavior::Mean;
+    use crate::election::voters::ApprovalThresholdBehavior::{Mean, TopFraction};
+    use rand::rngs::StdRng;
 
     // Unit tests for HonestVoter
     #[test]
@@ -217,6 +299,38 @@ mod tests {
         );
     }
 
+    #[test]
+    fn indifferent_voter_ballots() {
+        let mut scaled = HonestVoter::new(vec![0.8; 4], true, Mean);
+        let mut unscaled = HonestVoter::new(vec![0.8; 4], false, Mean);
+        assert!(scaled.is_indifferent());
+        assert!(!HonestVoter::new(vec![0.8, 0.8, 0.7], true, Mean).is_indifferent());
+
+        let candidates = (0..4).map(CandidateID).collect::<Vec<_>>();
+        let descending = candidates.iter().rev().copied().collect::<Vec<_>>();
+        assert!(scaled.cast_ordinal_ballot(OrdinalEnum::plurality).is_empty());
+        assert_eq!(scaled.cast_ordinal_equal_ballot("test"), &vec![descending]);
+        assert_eq!(scaled.cast_cardinal_ballot(10, CardinalEnum::score_10), &vec![5; 4]);
+        assert_eq!(unscaled.cast_cardinal_ballot(10, CardinalEnum::score_10), &vec![8; 4]);
+        assert_eq!(scaled.cast_approval_ballot(CardinalEnum::approval), &candidates);
+    }
+
+    #[test]
+    fn set_utilities_recomputes_ballots() {
+        let mut voter = HonestVoter::new(vec![0.3, 0.5, 0.1], false, Mean);
+        voter.precompute_cardinal_ballots(&[10]);
+        voter.set_utilities(vec![0.6, 0.2, 0.9]);
+        assert_eq!(
+            voter.cast_ordinal_ballot(OrdinalEnum::plurality),
+            &vec![CandidateID(2), CandidateID(0), CandidateID(1)]
+        );
+        assert_eq!(voter.cast_cardinal_ballot(10, CardinalEnum::score_10), &vec![6, 2, 9]);
+        assert_eq!(
+            voter.cast_approval_ballot(CardinalEnum::approval),
+            &vec![CandidateID(0), CandidateID(2)]
+        );
+    }
+
     #[test]
     fn scales_correct() {
         let mut voter = HonestVoter::new(vec![0.3, 0.5, 0.1], true, Mean);
@@ -228,4 +342,56 @@ mod tests {
         let mut voter = HonestVoter::new(vec![0.3, 0.5, 0.1], false, Mean);
         assert_eq!(voter.cast_cardinal_ballot(10, CardinalEnum::score_10), &vec![3, 5, 1]);
     }
+
+    #[test]
+    fn precompute_cardinal_ballots_correct() {
+        let mut voter = HonestVoter::new(vec![0.3, 0.5, 0.1], false, Mean);
+        voter.precompute_cardinal_ballots(&[5, 10]);
+        assert_eq!(voter.cached_cardinal_ballots.len(), 2);
+        assert_eq!(voter.cached_cardinal_ballots.get(&10), Some(&vec![3, 5, 1]));
+
+        // Casting a precomputed range shouldn't add anything new to the cache
+        assert_eq!(voter.cast_cardinal_ballot(10, CardinalEnum::score_10), &vec![3, 5, 1]);
+        assert_eq!(voter.cast_cardinal_ballot(5, CardinalEnum::score_5), &vec![2, 3, 1]);
+        assert_eq!(voter.cached_cardinal_ballots.len(), 2);
+    }
+
+    #[test]
+    fn rounding_modes_correct() {
+        let utilities = vec![0.4, 0.5, 0.1];
+        let mut round = HonestVoter::new(utilities.clone(), false, Mean);
+        assert_eq!(round.cast_cardinal_ballot(4, CardinalEnum::score_5), &vec![2, 2, 0]);
+        let mut floor =
+            HonestVoter::new(utilities.clone(), false, Mean).with_rounding(RoundingMode::Floor);
+        assert_eq!(floor.cast_cardinal_ballot(4, CardinalEnum::score_5), &vec![1, 2, 0]);
+        let mut ceil =
+            HonestVoter::new(utilities.clone(), false, Mean).with_rounding(RoundingMode::Ceil);
+        assert_eq!(ceil.cast_cardinal_ballot(4, CardinalEnum::score_5), &vec![2, 2, 1]);
+    }
+
+    #[test]
+    fn stochastic_rounding_in_bounds() {
+        use rand::SeedableRng;
+        let rng = StdRng::seed_from_u64(0);
+        let mut voter = HonestVoter::new(vec![0.4, 0.5, 0.1], false, Mean)
+            .with_rounding(RoundingMode::Stochastic(Box::new(rng)));
+        let ballot = voter.cast_cardinal_ballot(4, CardinalEnum::score_5).clone();
+        assert!(ballot[0] == 1 || ballot[0] == 2);
+        assert_eq!(ballot[1], 2);
+        assert!(ballot[2] == 0 || ballot[2] == 1);
+    }
+
+    #[test]
+    fn top_fraction_approval_correct() {
+        let utilities = vec![0.3, 0.5, 0.1, 0.4];
+        let mut half = HonestVoter::new(utilities.clone(), false, TopFraction(0.5));
+        assert_eq!(
+            half.cast_approval_ballot(CardinalEnum::approval),
+            &vec![CandidateID(1), CandidateID(3)]
+        );
+        let mut none = HonestVoter::new(utilities.clone(), false, TopFraction(0.0));
+        assert_eq!(none.cast_approval_ballot(CardinalEnum::approval), &vec![CandidateID(1)]);
+        let mut rounded_up = HonestVoter::new(utilities, false, TopFraction(0.6));
+        assert_eq!(rounded_up.cast_approval_ballot(CardinalEnum::approval).len(), 3);
+    }
 }
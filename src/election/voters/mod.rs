@@ -2,7 +2,14 @@ mod voters;
 mod honest_voter;
 mod real_ordinal_voter;
 mod real_cardinal_voter;
+mod strategic_score_voter;
+mod noisy_voter;
+mod betraying_voter;
 
 pub use voters::*;
 pub use honest_voter::*;
-pub use real_ordinal_voter::RealOrdinalVoter;
\ No newline at end of file
+pub use real_ordinal_voter::{honest_voters_from_ordinal_counts, RealOrdinalVoter};
+pub use real_cardinal_voter::RealCardinalVoter;
+pub use strategic_score_voter::StrategicScoreVoter;
+pub use noisy_voter::NoisyVoter;
+pub use betraying_voter::BetrayingVoter;
\ No newline at end of file
@@ -0,0 +1,143 @@
+//! This mod contains the implementation for the NoisyVoter struct, which represents a voter who
+//! misperceives the candidates, so that their ballots are random.
+
+use crate::election::election_profile::CandidateID;
+use crate::election::voters::*;
+use rand::rngs::StdRng;
+use rand::Rng;
+use rand_distr::Normal;
+use std::cmp::Ordering;
+
+use crate::election::election_methods::CardinalEnum;
+use crate::election::election_methods::OrdinalEnum;
+
+/// A NoisyVoter votes honestly, but off of a noisy perception of their utilities: each perceived
+/// utility is their true utility plus normally distributed noise, clamped back into [0, 1]. Every
+/// redraw_ballots call draws a new perception, and every ballot is cast from the current one, so
+/// a single election sees consistent ballots. Honest preferences and utilities are always the
+/// voter's true ones.
+#[derive(Clone)]
+pub struct NoisyVoter {
+    /// A vector containing this voter's true assessment of the utility the candidates provide
+    utilities: Vec<f64>,
+
+    /// The distribution of the noise added to each utility
+    noise: Normal<f64>,
+
+    /// The RNG the noise is drawn from, boxed to keep the Voters enum small
+    rng: Box<StdRng>,
+
+    /// An honest voter with the currently perceived utilities, who casts this voter's ballots
+    perceived: HonestVoter,
+}
+
+impl NoisyVoter {
+    const WARNING_STRING: &'static str =
+        "NoisyVoter noise must have a finite, non-negative standard deviation!";
+
+    /// Create a voter whose perceived utilities have noise with standard deviation noise_std_dev.
+    /// scales and threshold_behavior apply to the perceived utilities as for an HonestVoter.
+    pub fn new(
+        utilities: Vec<f64>,
+        scales: bool,
+        threshold_behavior: ApprovalThresholdBehavior,
+        noise_std_dev: f64,
+        rng: StdRng,
+    ) -> Self {
+        let noise = Normal::new(0f64, noise_std_dev)
+            .unwrap_or_else(|_| panic!("{}", NoisyVoter::WARNING_STRING));
+        let perceived = HonestVoter::new(utilities.clone(), scales, threshold_behavior);
+        let mut voter = Self {
+            utilities,
+            noise,
+            rng: Box::new(rng),
+            perceived,
+        };
+        voter.redraw_ballots();
+        voter
+    }
+}
+
+impl Voter for NoisyVoter {
+    /// Ordinal ballots are cast honestly from the perceived utilities
+    fn cast_ordinal_ballot(&mut self, method: OrdinalEnum) -> &Vec<CandidateID> {
+        self.perceived.cast_ordinal_ballot(method)
+    }
+
+    /// Ordinal-equal ballots are cast honestly from the perceived utilities
+    fn cast_ordinal_equal_ballot(&mut self, method_name: &str) -> &Vec<Vec<CandidateID>> {
+        self.perceived.cast_ordinal_equal_ballot(method_name)
+    }
+
+    /// Cardinal ballots are cast honestly from the perceived utilities
+    fn cast_cardinal_ballot(&mut self, range: usize, method: CardinalEnum) -> &Vec<usize> {
+        self.perceived.cast_cardinal_ballot(range, method)
+    }
+
+    /// Approval ballots are cast honestly from the perceived utilities
+    fn cast_approval_ballot(&mut self, method: CardinalEnum) -> &Vec<CandidateID> {
+        self.perceived.cast_approval_ballot(method)
+    }
+
+    fn honest_preference(&self, first: CandidateID, second: CandidateID) -> Ordering {
+        self.utilities[first.0]
+            .partial_cmp(&self.utilities[second.0])
+            .unwrap()
+    }
+
+    fn utilities(&self) -> &Vec<f64> {
+        &self.utilities
+    }
+
+    fn candidate_utility(&self, CandidateID(id): CandidateID) -> f64 {
+        self.utilities[id]
+    }
+
+    fn num_candidates(&self) -> usize {
+        self.utilities.len()
+    }
+
+    /// The candidate is dropped from both the true and the currently perceived utilities
+    fn remove_candidate(&mut self, candidate: CandidateID) {
+        self.utilities.remove(candidate.0);
+        self.perceived.remove_candidate(candidate);
+    }
+
+    /// Draws a new perception of the utilities
+    fn redraw_ballots(&mut self) {
+        let perceived_utilities = self
+            .utilities
+            .iter()
+            .map(|&utility| (utility + self.rng.sample(self.noise)).clamp(0f64, 1f64))
+            .collect();
+        self.perceived = self.perceived.with_utilities(perceived_utilities);
+    }
+}
+
+/// Unit tests for this module
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::election::voters::ApprovalThresholdBehavior::Mean;
+    use rand::SeedableRng;
+
+    #[test]
+    fn ballots_change_only_on_redraw() {
+        let mut voter = NoisyVoter::new(
+            vec![0.5, 0.45, 0.4],
+            false,
+            Mean,
+            0.2,
+            StdRng::seed_from_u64(701),
+        );
+        let mut ballots = Vec::new();
+        for _ in 0..50 {
+            let ballot = voter.cast_ordinal_ballot(OrdinalEnum::irv).clone();
+            assert_eq!(voter.cast_ordinal_ballot(OrdinalEnum::irv), &ballot);
+            ballots.push(ballot);
+            voter.redraw_ballots();
+        }
+        assert!(ballots.iter().any(|ballot| ballot != &ballots[0]));
+        assert_eq!(voter.utilities(), &vec![0.5, 0.45, 0.4]);
+    }
+}
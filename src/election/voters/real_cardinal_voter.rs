@@ -1,20 +1,29 @@
 //! A mod to hold the struct and implementation to represent real-world cardinal ballots of fixed
 //! range.
 
-use super::voters::Voter;
+use super::voters::{ballot_without, Voter};
 use crate::election::election_profile::CandidateID;
 use crate::election::election_methods::OrdinalEnum;
 use crate::election::election_methods::CardinalEnum;
 use crate::utility_functions::*;
+use rand::Rng;
+use rand_distr::Normal;
 use std::collections::HashMap;
 use std::cmp::Ordering;
 use std::cmp::Reverse;
 
 /// A struct that represents a real cardinal ballot of fixed range (i.e. a real voter's 0-10 score
 /// ballot).
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct RealCardinalVoter {
     range: usize,
+    /// The maximum score each candidate may receive on this ballot; candidate_caps[i] is the cap
+    /// for CandidateID(i). Unless otherwise specified, every candidate's cap is just range.
+    candidate_caps: Vec<usize>,
+    /// Whether each candidate was left blank on this ballot, rather than explicitly scored;
+    /// blanks[i] is true if CandidateID(i) was left blank. Blank candidates are scored as 0 in
+    /// cardinal_ballot.
+    blanks: Vec<bool>,
     cardinal_ballot: Vec<usize>,
     approval_ballot: Option<Vec<CandidateID>>,
     ordinal_equal_ballot: Vec<Vec<CandidateID>>,
@@ -29,6 +38,32 @@ impl RealCardinalVoter {
         ballot: Vec<usize>,
         tiebreaker: F,
     ) -> Self {
+        let candidate_caps = vec![range; ballot.len()];
+        Self::with_caps(range, ballot, candidate_caps, tiebreaker)
+    }
+
+    /// Create a new RealCardinalVoter whose candidates may have differing maximum scores (i.e. a
+    /// write-in candidate that can only be scored up to some lower value). candidate_caps[i] is the
+    /// maximum score of CandidateID(i), and must not exceed range. Methods which assume a uniform
+    /// range still use range. Panics if any score on the ballot exceeds its candidate's cap.
+    pub fn with_caps<F: Fn(&usize, &usize) -> Ordering + Copy>(
+        range: usize,
+        ballot: Vec<usize>,
+        candidate_caps: Vec<usize>,
+        tiebreaker: F,
+    ) -> Self {
+        // Validate the ballot against the caps
+        if candidate_caps.len() != ballot.len() || candidate_caps.iter().any(|&cap| cap > range) {
+            panic!("{}", RealCardinalVoter::CAP_WARNING)
+        }
+        if ballot
+            .iter()
+            .zip(candidate_caps.iter())
+            .any(|(score, cap)| score > cap)
+        {
+            panic!("{}", RealCardinalVoter::SCORE_WARNING)
+        }
+
         // Build approval ballot from the cast cardinal ballot
         let approval_ballot = if range == 1 {
             Some(
@@ -64,6 +99,8 @@ impl RealCardinalVoter {
 
         Self {
             range,
+            blanks: vec![false; candidate_caps.len()],
+            candidate_caps,
             cardinal_ballot: ballot,
             approval_ballot,
             ordinal_equal_ballot,
@@ -71,6 +108,60 @@ impl RealCardinalVoter {
         }
     }
 
+    /// Create a new RealCardinalVoter from a given ballot and range after perturbing each score by
+    /// Gaussian noise with standard deviation noise_std, rounding to the nearest rating and clamping
+    /// back into [0, range]. This models measurement error in real ballot data. The ordinal,
+    /// ordinal-equal, and approval ballots are derived from the perturbed scores.
+    pub fn with_noise<R: Rng, F: Fn(&usize, &usize) -> Ordering + Copy>(
+        range: usize,
+        ballot: Vec<usize>,
+        noise_std: f64,
+        rng: &mut R,
+        tiebreaker: F,
+    ) -> Self {
+        let normal = Normal::new(0f64, noise_std).unwrap();
+        let ballot = ballot
+            .into_iter()
+            .map(|score| {
+                let perturbed = score as f64 + rng.sample(normal);
+                perturbed.round().clamp(0f64, range as f64) as usize
+            })
+            .collect();
+        Self::new(range, ballot, tiebreaker)
+    }
+
+    /// Create a new RealCardinalVoter from a ballot on which some candidates may have been left
+    /// blank (None) instead of scored. Blank candidates are scored as 0 wherever a complete
+    /// ballot is needed; use score to distinguish a blank from an explicit 0.
+    pub fn with_blanks<F: Fn(&usize, &usize) -> Ordering + Copy>(
+        range: usize,
+        ballot: Vec<Option<usize>>,
+        tiebreaker: F,
+    ) -> Self {
+        let blanks = ballot.iter().map(|score| score.is_none()).collect();
+        let mut voter = Self::new(
+            range,
+            ballot.into_iter().map(|score| score.unwrap_or(0)).collect(),
+            tiebreaker,
+        );
+        voter.blanks = blanks;
+        voter
+    }
+
+    /// Returns the score this voter gave the candidate, or None if they left it blank
+    pub fn score(&self, CandidateID(id): CandidateID) -> Option<usize> {
+        if self.blanks[id] {
+            None
+        } else {
+            Some(self.cardinal_ballot[id])
+        }
+    }
+
+    /// Get a reference to the vec of per-candidate maximum scores
+    pub fn candidate_caps(&self) -> &Vec<usize> {
+        &self.candidate_caps
+    }
+
     const UTILITY_WARNING: &'static str = "A RealCardinalVoter does not contain raw \
     utility information!";
 
@@ -79,6 +170,12 @@ impl RealCardinalVoter {
 
     const RANGE_WARNING: &'static str = "Invalid range of ballot ratings requested \
     from RealCardinalVoter";
+
+    const CAP_WARNING: &'static str = "A RealCardinalVoter requires one cap per candidate, \
+    none of which may exceed the ballot's range!";
+
+    const SCORE_WARNING: &'static str = "A RealCardinalVoter's ballot gave a candidate a score \
+    above that candidate's cap!";
 }
 
 impl Voter for RealCardinalVoter {
@@ -117,4 +214,101 @@ impl Voter for RealCardinalVoter {
     fn candidate_utility(&self, _: CandidateID) -> f64 {
         panic!("{}", RealCardinalVoter::UTILITY_WARNING)
     }
+
+    fn num_candidates(&self) -> usize {
+        self.cardinal_ballot.len()
+    }
+
+    fn remove_candidate(&mut self, candidate: CandidateID) {
+        self.candidate_caps.remove(candidate.0);
+        self.blanks.remove(candidate.0);
+        self.cardinal_ballot.remove(candidate.0);
+        if let Some(approval_ballot) = self.approval_ballot.as_mut() {
+            *approval_ballot = ballot_without(approval_ballot, candidate);
+        }
+        self.ordinal_equal_ballot = self
+            .ordinal_equal_ballot
+            .iter()
+            .map(|tier| ballot_without(tier, candidate))
+            .filter(|tier| !tier.is_empty())
+            .collect();
+        self.ordinal_ballot = ballot_without(&self.ordinal_ballot, candidate);
+    }
+}
+
+/// Unit tests for this module
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn remove_candidate_relabels() {
+        let mut voter = RealCardinalVoter::new(10, vec![3, 10, 3, 7], higher_index_wins());
+        voter.remove_candidate(CandidateID(1));
+        assert_eq!(voter.cast_cardinal_ballot(10, CardinalEnum::score_10), &vec![3, 3, 7]);
+        assert_eq!(
+            voter.cast_ordinal_ballot(OrdinalEnum::plurality),
+            &vec![CandidateID(2), CandidateID(1), CandidateID(0)]
+        );
+        assert_eq!(
+            voter.cast_ordinal_equal_ballot("test"),
+            &vec![vec![CandidateID(2)], vec![CandidateID(0), CandidateID(1)]]
+        );
+        assert_eq!(voter.num_candidates(), 3);
+    }
+
+    #[test]
+    fn caps_default_to_range() {
+        let voter = RealCardinalVoter::new(10, vec![3, 10, 0], higher_index_wins());
+        assert_eq!(voter.candidate_caps(), &vec![10, 10, 10]);
+    }
+
+    #[test]
+    fn caps_accepted() {
+        let mut voter =
+            RealCardinalVoter::with_caps(10, vec![3, 10, 5], vec![10, 10, 5], higher_index_wins());
+        assert_eq!(voter.candidate_caps(), &vec![10, 10, 5]);
+        assert_eq!(voter.cast_cardinal_ballot(10, CardinalEnum::score_10), &vec![3, 10, 5]);
+    }
+
+    #[test]
+    fn zero_noise_unchanged() {
+        let mut rng = rand::thread_rng();
+        let mut voter =
+            RealCardinalVoter::with_noise(10, vec![3, 10, 0], 0.0, &mut rng, higher_index_wins());
+        assert_eq!(voter.cast_cardinal_ballot(10, CardinalEnum::score_10), &vec![3, 10, 0]);
+        assert_eq!(
+            voter.cast_ordinal_ballot(OrdinalEnum::plurality),
+            &vec![CandidateID(1), CandidateID(0), CandidateID(2)]
+        );
+    }
+
+    #[test]
+    fn noise_stays_in_range() {
+        let mut rng = rand::thread_rng();
+        for _ in 0..100 {
+            let mut voter =
+                RealCardinalVoter::with_noise(5, vec![0, 5, 2], 3.0, &mut rng, higher_index_wins());
+            assert!(voter
+                .cast_cardinal_ballot(5, CardinalEnum::score_5)
+                .iter()
+                .all(|&score| score <= 5));
+        }
+    }
+
+    #[test]
+    fn blanks_distinguished_from_zero() {
+        let mut voter =
+            RealCardinalVoter::with_blanks(10, vec![Some(0), None, Some(7)], higher_index_wins());
+        assert_eq!(voter.cast_cardinal_ballot(10, CardinalEnum::score_10), &vec![0, 0, 7]);
+        assert_eq!(voter.score(CandidateID(0)), Some(0));
+        assert_eq!(voter.score(CandidateID(1)), None);
+        assert_eq!(voter.score(CandidateID(2)), Some(7));
+    }
+
+    #[test]
+    #[should_panic]
+    fn over_cap_score_rejected() {
+        RealCardinalVoter::with_caps(10, vec![3, 10, 6], vec![10, 10, 5], higher_index_wins());
+    }
 }
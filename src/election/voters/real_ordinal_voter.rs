@@ -1,13 +1,33 @@
 //! A mod to hold the struct and implementation to represent real-world ordinal ballots.
 
+use crate::election::candidate_registry::{CandidateRegistry, UnknownCandidate};
 use crate::election::election_profile::CandidateID;
-use crate::election::voters::Voter;
+use crate::election::voters::{ballot_without, Voter};
 use std::cmp::Ordering;
 use crate::election::election_methods::CardinalEnum;
 use crate::election::election_methods::OrdinalEnum;
 
+const COUNTS_WARNING: &str = "A ballot ranks a candidate outside of num_candidates!";
+
+/// Expand a profile written as (ballot, count) pairs, i.e. the "24: A > B > C" style, into an
+/// electorate with count voters casting each ballot, in the order given. Panics if any ballot
+/// ranks a candidate with an ID of num_candidates or more.
+pub fn honest_voters_from_ordinal_counts(
+    counts: &[(Vec<CandidateID>, usize)],
+    num_candidates: usize,
+) -> Vec<RealOrdinalVoter> {
+    let mut voters = Vec::new();
+    for (ballot, count) in counts {
+        if ballot.iter().any(|&CandidateID(id)| id >= num_candidates) {
+            panic!("{}", COUNTS_WARNING);
+        }
+        (0..*count).for_each(|_| voters.push(RealOrdinalVoter::new(ballot.clone())));
+    }
+    voters
+}
+
 /// A struct that represents an actual cast ordinal ballot
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct RealOrdinalVoter {
     ordinal_ballot: Vec<CandidateID>
 }
@@ -19,11 +39,43 @@ impl RealOrdinalVoter {
     const WARNING_STRING_EQUALITY: &'static str =
         "RealOrdinalVoter does not permit equalities on a ballot!";
 
+    const WARNING_STRING_DUPLICATE: &'static str =
+        "RealOrdinalVoter cannot rank the same candidate twice!";
+
     pub fn new(ballot: Vec<CandidateID>) -> Self {
         Self {
             ordinal_ballot: ballot,
         }
     }
+
+    /// Builds a voter from a ranking of candidate names, most preferred first, looking up each
+    /// name's CandidateID in registry. Returns an error naming the first name that isn't
+    /// registered.
+    pub fn from_names(
+        ranking: &[&str],
+        registry: &CandidateRegistry,
+    ) -> Result<Self, UnknownCandidate> {
+        let ballot = ranking
+            .iter()
+            .map(|name| registry.id(name))
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(Self::new(ballot))
+    }
+
+    /// Returns the ballot this voter cast
+    pub fn ballot(&self) -> &Vec<CandidateID> {
+        &self.ordinal_ballot
+    }
+
+    /// Appends a candidate to the bottom of this voter's ballot.
+    ///
+    /// Panics if the candidate is already ranked on the ballot.
+    pub fn append_preference(&mut self, candidate: CandidateID) {
+        if self.ordinal_ballot.contains(&candidate) {
+            panic!("{}", RealOrdinalVoter::WARNING_STRING_DUPLICATE)
+        }
+        self.ordinal_ballot.push(candidate);
+    }
 }
 
 impl Voter for RealOrdinalVoter {
@@ -54,4 +106,71 @@ impl Voter for RealOrdinalVoter {
     fn candidate_utility(&self, _: CandidateID) -> f64 {
         panic!("{}", RealOrdinalVoter::WARNING_STRING)
     }
+
+    /// Since the ballot may be truncated, this is one more than the highest ranked candidate's
+    /// index.
+    fn num_candidates(&self) -> usize {
+        self.ordinal_ballot
+            .iter()
+            .map(|&CandidateID(id)| id + 1)
+            .max()
+            .unwrap_or(0)
+    }
+
+    fn may_be_truncated(&self) -> bool {
+        true
+    }
+
+    fn remove_candidate(&mut self, candidate: CandidateID) {
+        self.ordinal_ballot = ballot_without(&self.ordinal_ballot, candidate);
+    }
+}
+
+/// Unit tests for this module
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::election::election_methods::tests::irv_differs;
+    use crate::election::ElectionMethods;
+    use crate::utility_functions::higher_index_wins;
+    use std::collections::HashMap;
+
+    #[test]
+    fn test_from_names() {
+        let registry = CandidateRegistry::from_names(&["Alice", "Bob", "Carol"]);
+        let voter = RealOrdinalVoter::from_names(&["Bob", "Alice"], &registry).unwrap();
+        assert_eq!(voter.ballot(), &vec![CandidateID(1), CandidateID(0)]);
+
+        assert_eq!(
+            RealOrdinalVoter::from_names(&["Bob", "Dave"], &registry).unwrap_err(),
+            UnknownCandidate("Dave".to_string())
+        );
+    }
+
+    #[test]
+    fn test_from_ordinal_counts() {
+        let ballot = |ids: [usize; 5]| ids.map(CandidateID).to_vec();
+        let counts = [
+            (ballot([0, 1, 2, 3, 4]), 24),
+            (ballot([1, 0, 2, 3, 4]), 24),
+            (ballot([2, 3, 4, 1, 0]), 20),
+            (ballot([3, 4, 2, 0, 1]), 20),
+            (ballot([4, 2, 3, 0, 1]), 12),
+        ];
+        let mut voters = honest_voters_from_ordinal_counts(&counts, 5);
+        assert_eq!(voters.len(), 100);
+        assert_eq!(voters[24].ballot(), &counts[1].0);
+
+        // Both electorates cast the same ballots, the same number of times each
+        let mut honest = irv_differs();
+        let mut tally = HashMap::new();
+        for voter in honest.iter_mut() {
+            *tally.entry(voter.cast_ordinal_ballot(OrdinalEnum::irv).clone()).or_insert(0) += 1;
+        }
+        assert_eq!(tally, counts.iter().cloned().collect::<HashMap<_, _>>());
+        assert_eq!(
+            ElectionMethods::irv(&mut voters, 5, higher_index_wins()),
+            ElectionMethods::irv(&mut irv_differs(), 5, higher_index_wins())
+        );
+    }
 }
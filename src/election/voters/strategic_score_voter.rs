@@ -0,0 +1,157 @@
+//! This mod contains the implementation for the StrategicScoreVoter struct, which represents a
+//! voter who min-maxes the two frontrunners on a score ballot.
+
+use crate::election::election_profile::CandidateID;
+use crate::election::voters::*;
+use std::cmp::Ordering;
+use std::collections::HashMap;
+
+use crate::election::election_methods::CardinalEnum;
+use crate::election::election_methods::OrdinalEnum;
+
+/// A StrategicScoreVoter casts the canonical score voting strategy: given the two frontrunners,
+/// they give the maximum score to whichever frontrunner they prefer and the minimum score to the
+/// other, while scoring every other candidate honestly. If they are indifferent between the
+/// frontrunners, both are scored honestly. All other ballots are cast honestly.
+#[derive(Clone)]
+pub struct StrategicScoreVoter {
+    /// The honest voter whose utilities and non-cardinal ballots this voter uses
+    honest_voter: HonestVoter,
+
+    /// The two candidates this voter believes are the frontrunners
+    frontrunners: (CandidateID, CandidateID),
+
+    /// Since the frontrunners never change, the strategic cardinal ballot for a given range
+    /// should never change either.
+    cached_cardinal_ballots: HashMap<usize, Vec<usize>>,
+}
+
+impl StrategicScoreVoter {
+    pub fn new(
+        utilities: Vec<f64>,
+        scales: bool,
+        threshold_behavior: ApprovalThresholdBehavior,
+        frontrunners: (CandidateID, CandidateID),
+    ) -> Self {
+        Self {
+            honest_voter: HonestVoter::new(utilities, scales, threshold_behavior),
+            frontrunners,
+            cached_cardinal_ballots: HashMap::new(),
+        }
+    }
+
+    /// Returns the two candidates this voter treats as the frontrunners
+    pub fn frontrunners(&self) -> (CandidateID, CandidateID) {
+        self.frontrunners
+    }
+}
+
+impl Voter for StrategicScoreVoter {
+    /// Ordinal ballots are cast honestly
+    fn cast_ordinal_ballot(&mut self, method: OrdinalEnum) -> &Vec<CandidateID> {
+        self.honest_voter.cast_ordinal_ballot(method)
+    }
+
+    /// Ordinal-equal ballots are cast honestly
+    fn cast_ordinal_equal_ballot(&mut self, method_name: &str) -> &Vec<Vec<CandidateID>> {
+        self.honest_voter.cast_ordinal_equal_ballot(method_name)
+    }
+
+    /// Returns the honest rating in [0, range] for each candidate, except that the preferred
+    /// frontrunner is rated range and the other frontrunner is rated 0.
+    fn cast_cardinal_ballot(&mut self, range: usize, method: CardinalEnum) -> &Vec<usize> {
+        if !self.cached_cardinal_ballots.contains_key(&range) {
+            let mut ballot = self.honest_voter.cast_cardinal_ballot(range, method).clone();
+            let (first, second) = self.frontrunners;
+            match self.honest_voter.honest_preference(first, second) {
+                Ordering::Greater => {
+                    ballot[first.0] = range;
+                    ballot[second.0] = 0;
+                }
+                Ordering::Less => {
+                    ballot[first.0] = 0;
+                    ballot[second.0] = range;
+                }
+                Ordering::Equal => {}
+            }
+            self.cached_cardinal_ballots.insert(range, ballot);
+        }
+        self.cached_cardinal_ballots.get(&range).unwrap()
+    }
+
+    /// Approval ballots are cast honestly
+    fn cast_approval_ballot(&mut self, method: CardinalEnum) -> &Vec<CandidateID> {
+        self.honest_voter.cast_approval_ballot(method)
+    }
+
+    fn honest_preference(&self, first: CandidateID, second: CandidateID) -> Ordering {
+        self.honest_voter.honest_preference(first, second)
+    }
+
+    fn utilities(&self) -> &Vec<f64> {
+        self.honest_voter.utilities()
+    }
+
+    fn candidate_utility(&self, id: CandidateID) -> f64 {
+        self.honest_voter.candidate_utility(id)
+    }
+
+    fn num_candidates(&self) -> usize {
+        self.honest_voter.num_candidates()
+    }
+
+    /// If a frontrunner is removed, the other is left as both frontrunners, so this voter scores
+    /// every candidate honestly
+    fn remove_candidate(&mut self, candidate: CandidateID) {
+        self.honest_voter.remove_candidate(candidate);
+        let (first, second) = self.frontrunners;
+        self.frontrunners = match (
+            relabel_after_removal(first, candidate),
+            relabel_after_removal(second, candidate),
+        ) {
+            (Some(first), Some(second)) => (first, second),
+            (Some(remaining), None) | (None, Some(remaining)) => (remaining, remaining),
+            (None, None) => (first, second),
+        };
+        self.cached_cardinal_ballots.clear();
+    }
+}
+
+/// Unit tests for this module
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::election::voters::ApprovalThresholdBehavior::Mean;
+
+    #[test]
+    fn frontrunners_get_extreme_scores() {
+        // Sincerely, A and C are only barely apart, and both are middling
+        let mut voter = StrategicScoreVoter::new(
+            vec![0.45, 0.9, 0.4, 0.1],
+            false,
+            Mean,
+            (CandidateID(0), CandidateID(2)),
+        );
+        assert_eq!(voter.cast_cardinal_ballot(10, CardinalEnum::score_10), &vec![10, 9, 0, 1]);
+
+        // Listing the frontrunners in the other order doesn't matter
+        let mut voter = StrategicScoreVoter::new(
+            vec![0.45, 0.9, 0.4, 0.1],
+            false,
+            Mean,
+            (CandidateID(2), CandidateID(0)),
+        );
+        assert_eq!(voter.cast_cardinal_ballot(5, CardinalEnum::score_5), &vec![5, 5, 0, 1]);
+    }
+
+    #[test]
+    fn indifferent_voter_scores_honestly() {
+        let mut voter = StrategicScoreVoter::new(
+            vec![0.5, 0.9, 0.5],
+            false,
+            Mean,
+            (CandidateID(0), CandidateID(2)),
+        );
+        assert_eq!(voter.cast_cardinal_ballot(10, CardinalEnum::score_10), &vec![5, 9, 5]);
+    }
+}
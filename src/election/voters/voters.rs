@@ -7,10 +7,14 @@ use crate::election::election_profile::CandidateID;
 use crate::election::election_methods::CardinalEnum;
 use crate::election::election_methods::OrdinalEnum;
 use enum_dispatch::enum_dispatch;
+use rand::rngs::StdRng;
 use std::cmp::Ordering;
+use std::sync::Arc;
 use voters::honest_voter::HonestVoter;
+use voters::noisy_voter::NoisyVoter;
 use voters::real_ordinal_voter::RealOrdinalVoter;
 use voters::real_cardinal_voter::RealCardinalVoter;
+use voters::strategic_score_voter::StrategicScoreVoter;
 use crate::election::voters;
 
 /// Trait to define a voter
@@ -46,27 +50,148 @@ pub trait Voter {
 
     /// Return the voter's honest utility assessment of candidate id
     fn candidate_utility(&self, _: CandidateID) -> f64;
+
+    /// Return the number of candidates this voter knows about: the length of their utility vec or
+    /// complete ballot. A voter whose ballot may be truncated instead returns the smallest number
+    /// of candidates consistent with their ballot.
+    fn num_candidates(&self) -> usize;
+
+    /// Whether this voter's ballot may be truncated, so that num_candidates may be fewer than the
+    /// candidates in the election. Voters with utilities or complete ballots never are.
+    fn may_be_truncated(&self) -> bool {
+        false
+    }
+
+    /// For voters whose ballots are random, draw new ballots, which are then cast until the next
+    /// redraw. Voters whose ballots are fixed do nothing.
+    fn redraw_ballots(&mut self) {}
+
+    /// Remove the candidate from the election, as if they had never run: they are dropped from
+    /// every ballot this voter casts, and every candidate with a higher ID moves down one so the
+    /// remaining IDs stay contiguous. Voters with utilities rebuild their ballots from the
+    /// remaining utilities; voters with real ballots keep their remaining rankings and scores.
+    fn remove_candidate(&mut self, candidate: CandidateID);
+
+    /// Cast the kind of ballot requested, by dispatching to the matching cast_* method. This lets
+    /// code handle ballots without knowing their format ahead of time, at the cost of a clone;
+    /// methods which know the format they need should call the cast_* methods directly.
+    fn cast(&mut self, kind: BallotKind) -> Ballot {
+        match kind {
+            BallotKind::Ordinal(method) => {
+                Ballot::Ordinal(self.cast_ordinal_ballot(method).clone())
+            }
+            BallotKind::OrdinalEqual(method_name) => {
+                Ballot::OrdinalEqual(self.cast_ordinal_equal_ballot(method_name).clone())
+            }
+            BallotKind::Cardinal(range, method) => {
+                Ballot::Cardinal(self.cast_cardinal_ballot(range, method).clone())
+            }
+            BallotKind::Approval(method) => {
+                Ballot::Approval(self.cast_approval_ballot(method).clone())
+            }
+        }
+    }
+}
+
+/// The kind of ballot to request from Voter::cast, along with the arguments its cast_* method
+/// takes.
+#[derive(Copy, Clone)]
+pub enum BallotKind<'a> {
+    /// An ordinal ballot, as from cast_ordinal_ballot
+    Ordinal(OrdinalEnum),
+    /// An ordinal ballot with equal rankings, as from cast_ordinal_equal_ballot
+    OrdinalEqual(&'a str),
+    /// A cardinal ballot with ratings in [0, range], as from cast_cardinal_ballot
+    Cardinal(usize, CardinalEnum),
+    /// An approval ballot, as from cast_approval_ballot
+    Approval(CardinalEnum),
+}
+
+/// A ballot of any format, as returned by Voter::cast. Each variant holds what the matching
+/// cast_* method returns.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Ballot {
+    Ordinal(Vec<CandidateID>),
+    OrdinalEqual(Vec<Vec<CandidateID>>),
+    Cardinal(Vec<usize>),
+    Approval(Vec<CandidateID>),
+}
+
+/// The ID candidate has once removed has been dropped from the election: every candidate with a
+/// higher ID moves down one, and removed itself has no ID (None).
+pub(crate) fn relabel_after_removal(
+    CandidateID(id): CandidateID,
+    CandidateID(removed): CandidateID,
+) -> Option<CandidateID> {
+    match id.cmp(&removed) {
+        Ordering::Less => Some(CandidateID(id)),
+        Ordering::Equal => None,
+        Ordering::Greater => Some(CandidateID(id - 1)),
+    }
+}
+
+/// Drop removed from ballot, relabeling the remaining candidates as for relabel_after_removal
+pub(crate) fn ballot_without(ballot: &[CandidateID], removed: CandidateID) -> Vec<CandidateID> {
+    ballot
+        .iter()
+        .filter_map(|&candidate| relabel_after_removal(candidate, removed))
+        .collect()
 }
 
 /// Enum for static polymorphism (enum dispatch) of all voters
 #[enum_dispatch(Voter)]
+#[derive(Clone)]
 pub enum Voters {
     HonestVoter,
     RealOrdinalVoter,
     RealCardinalVoter,
+    StrategicScoreVoter,
+    NoisyVoter,
 }
 
+/// A closure computing an approval threshold from a voter's utilities
+pub type ThresholdFn = Arc<dyn Fn(&Vec<f64>) -> f64 + Send + Sync>;
+
 /// Helper enum to indicate where a voter would honestly put their Approval threshold.
 /// Voters will cast an approval ballot in support of any candidate above or equal to the threshold.
 /// Note that regardless of threshold, the voter will always approve of at least one candidate
 /// (their favorite).
+#[derive(Clone)]
 pub enum ApprovalThresholdBehavior {
     /// Set by closure for custom behavior
-    Function(Box<dyn Fn(&Vec<f64>) -> f64>),
+    Function(ThresholdFn),
     /// Set as greater than or equal to the mean of utilities
     Mean,
     /// Set threshold directly
     Preset(f64),
+    /// Approve the top ceil(fraction * num_candidates) candidates by utility
+    TopFraction(f64),
+}
+
+/// Helper enum to indicate how a voter converts their (possibly scaled) utilities into integer
+/// ratings when casting a cardinal ballot with a given range.
+#[derive(Clone, Default)]
+pub enum RoundingMode {
+    /// Round to the nearest rating (the default)
+    #[default]
+    Round,
+    /// Always round down
+    Floor,
+    /// Always round up
+    Ceil,
+    /// Round up with probability equal to the fractional part, and down otherwise, using the
+    /// given RNG
+    Stochastic(Box<StdRng>),
+}
+
+/// Helper enum to indicate how blank (unscored) candidates on a RealCardinalVoter's ballot are
+/// counted by methods which average each candidate's scores.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum BlankPolicy {
+    /// A blank counts as a score of 0
+    TreatAsZero,
+    /// A blank is excluded from that candidate's average, as an abstention
+    Ignore,
 }
 
 /// Unit tests for this module
@@ -89,4 +214,41 @@ mod tests {
             println!("{:?}", voter.cast_ordinal_ballot(OrdinalEnum::plurality));
         }
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_cast_each_ballot_kind() {
+        let mut voter = HonestVoter::new(vec![0.6, 0.1, 1.0, 0.3], false, Mean);
+        let mut reference = voter.clone();
+        assert_eq!(
+            voter.cast(BallotKind::Ordinal(OrdinalEnum::irv)),
+            Ballot::Ordinal(reference.cast_ordinal_ballot(OrdinalEnum::irv).clone())
+        );
+        assert_eq!(
+            voter.cast(BallotKind::OrdinalEqual("test")),
+            Ballot::OrdinalEqual(reference.cast_ordinal_equal_ballot("test").clone())
+        );
+        assert_eq!(
+            voter.cast(BallotKind::Cardinal(10, CardinalEnum::score_10)),
+            Ballot::Cardinal(reference.cast_cardinal_ballot(10, CardinalEnum::score_10).clone())
+        );
+        assert_eq!(
+            voter.cast(BallotKind::Approval(CardinalEnum::approval)),
+            Ballot::Approval(vec![CandidateID(0), CandidateID(2)])
+        );
+    }
+
+    /// Voters with a closure threshold must still be shareable across threads
+    #[test]
+    fn function_threshold_is_send_and_sync() {
+        fn assert_send_sync<T: Send + Sync>(_: &T) {}
+        let threshold = ApprovalThresholdBehavior::Function(Arc::new(|utilities: &Vec<f64>| {
+            utilities.iter().copied().fold(f64::NAN, f64::max) / 2f64
+        }));
+        let mut voter = HonestVoter::new(vec![0.6, 0.1, 1.0, 0.3], false, threshold);
+        assert_send_sync(&voter);
+        assert_eq!(
+            voter.cast_approval_ballot(CardinalEnum::approval),
+            &vec![CandidateID(0), CandidateID(2)]
+        );
+    }
+}
@@ -0,0 +1,31 @@
+//! EMSim: a simulator for comparing election methods. The election logic lives in the election
+//! mod, with generators, metrics and helpers for building and evaluating elections alongside it.
+
+pub mod election;
+pub mod metrics;
+pub mod preflib;
+pub mod recording_rng;
+pub mod tie_breaker;
+pub mod utility_functions;
+pub mod utility_generators;
+
+/// Re-exports of the types needed to run a basic election.
+///
+/// ```
+/// use emsim::prelude::*;
+/// use emsim::election::ApprovalThresholdBehavior;
+/// use emsim::utility_functions::higher_index_wins;
+///
+/// let mut voters = vec![
+///     HonestVoter::new(vec![0.9, 0.1, 0.3], false, ApprovalThresholdBehavior::Mean),
+///     HonestVoter::new(vec![0.2, 0.8, 0.5], false, ApprovalThresholdBehavior::Mean),
+///     HonestVoter::new(vec![0.7, 0.0, 0.6], false, ApprovalThresholdBehavior::Mean),
+/// ];
+/// let results = ElectionMethods::plurality(&mut voters, 3, higher_index_wins());
+/// assert_eq!(results[0], CandidateID(0));
+/// ```
+pub mod prelude {
+    pub use crate::election::{
+        CandidateID, CardinalEnum, ElectionMethods, HonestVoter, OrdinalEnum, Voter,
+    };
+}
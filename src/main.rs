@@ -1,14 +1,10 @@
 extern crate core;
 
-use crate::election::ElectionMethods;
+use emsim::election::ElectionMethods;
+use emsim::utility_generators;
 use rand::thread_rng;
 use rand_distr::Beta;
 
-mod election;
-mod utility_generators;
-mod metrics;
-mod utility_functions;
-
 fn main() {
     println!("Number of ordinal methods currently implemented: {}", ElectionMethods::METHOD_COUNT_ordinal);
     ElectionMethods::METHOD_LIST_ordinal.iter()
@@ -1,6 +1,8 @@
 //! mod containing metrics for spatial-based distance calculations. For the moment, only holds
-//! a generic L_p metric function.
+//! a generic L_p metric function, along with a numerically stable variant for large p.
+//! Additionally holds metrics for comparing rankings of candidates.
 
+use crate::election::CandidateID;
 
 /// The L_p metric, where p is passed in a const generic argument. For those unfamiliar, p = 1 is
 /// taxicab distance and p = 2 is the familiar Euclidean distance.
@@ -11,4 +13,107 @@ pub fn lp_metric<const P: usize>(location_1: &Vec<f64>, location_2: &Vec<f64>) -
         .map(|(&x1, &x2)| (x1 - x2).abs().powi(P as i32))
         .sum::<f64>()
         .powf(1f64 / P as f64)
-}
\ No newline at end of file
+}
+/// A numerically stable version of the L_p metric. Rather than summing |x1 - x2|^p directly, which
+/// overflows to infinity for large p and large coordinate differences, the largest coordinate
+/// difference m is factored out so the result is computed as m * (sum of (|x1 - x2| / m)^p)^(1/p).
+/// Every ratio lies in [0, 1], so as p grows the result approaches the Chebyshev (L_inf) distance
+/// instead of overflowing.
+pub fn lp_metric_stable<const P: usize>(location_1: &Vec<f64>, location_2: &Vec<f64>) -> f64 {
+    let max = location_1
+        .into_iter()
+        .zip(location_2.into_iter())
+        .map(|(&x1, &x2)| (x1 - x2).abs())
+        .fold(0f64, f64::max);
+    if max == 0f64 {
+        return 0f64;
+    }
+    max * location_1
+        .into_iter()
+        .zip(location_2.into_iter())
+        .map(|(&x1, &x2)| ((x1 - x2).abs() / max).powi(P as i32))
+        .sum::<f64>()
+        .powf(1f64 / P as f64)
+}
+
+/// The Kendall tau rank correlation between two rankings of the same set of candidates. Returns 1.0
+/// if the rankings are identical, -1.0 if one is the reverse of the other, and otherwise the
+/// difference between the number of concordant and discordant pairs divided by the total number of
+/// pairs. Panics if a candidate in a is missing from b.
+pub fn kendall_tau(a: &[CandidateID], b: &[CandidateID]) -> f64 {
+    let n = a.len();
+    if n < 2 {
+        return 1f64;
+    }
+
+    // Position of each candidate in ranking b
+    let max_id = b.iter().map(|&CandidateID(id)| id + 1).max().unwrap_or(0);
+    let mut position = vec![None; max_id];
+    b.iter()
+        .enumerate()
+        .for_each(|(i, &CandidateID(id))| position[id] = Some(i));
+    let position: Vec<usize> = a
+        .iter()
+        .map(|&CandidateID(id)| {
+            position
+                .get(id)
+                .copied()
+                .flatten()
+                .expect("A candidate in ranking a is missing from ranking b!")
+        })
+        .collect();
+
+    let mut concordant = 0i64;
+    let mut discordant = 0i64;
+    for i in 0..n {
+        for j in (i + 1)..n {
+            // a ranks a[i] ahead of a[j], so the pair is concordant if b does as well
+            if position[i] < position[j] {
+                concordant += 1;
+            } else {
+                discordant += 1;
+            }
+        }
+    }
+    (concordant - discordant) as f64 / (n * (n - 1) / 2) as f64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lp_metric_stable_matches_naive() {
+        let (a, b) = (vec![0.0, 1.0, 2.0], vec![3.0, -1.0, 2.5]);
+        assert!((lp_metric::<2>(&a, &b) - lp_metric_stable::<2>(&a, &b)).abs() < 1e-12);
+        assert!((lp_metric::<1>(&a, &b) - lp_metric_stable::<1>(&a, &b)).abs() < 1e-12);
+        assert_eq!(lp_metric_stable::<2>(&a, &a), 0.0);
+    }
+
+    #[test]
+    fn test_lp_metric_stable_large_p() {
+        let (a, b) = (vec![0.0, 0.0], vec![1e10, 5e9]);
+        assert!(lp_metric::<50>(&a, &b).is_infinite());
+        let stable = lp_metric_stable::<50>(&a, &b);
+        assert!(stable.is_finite());
+        assert!((stable - 1e10).abs() / 1e10 < 1e-6);
+    }
+
+    #[test]
+    fn test_kendall_tau() {
+        let a = vec![CandidateID(2), CandidateID(1), CandidateID(0)];
+        let reversed = vec![CandidateID(0), CandidateID(1), CandidateID(2)];
+        let swapped = vec![CandidateID(1), CandidateID(2), CandidateID(0)];
+        assert_eq!(kendall_tau(&a, &a), 1.0);
+        assert_eq!(kendall_tau(&a, &reversed), -1.0);
+        assert!((kendall_tau(&a, &swapped) - 1.0 / 3.0).abs() < 1e-12);
+    }
+
+    #[test]
+    #[should_panic(expected = "missing from ranking b")]
+    fn test_kendall_tau_mismatched_rankings() {
+        let a = vec![CandidateID(2), CandidateID(1), CandidateID(0)];
+        let b = vec![CandidateID(0), CandidateID(3), CandidateID(2)];
+        kendall_tau(&a, &b);
+    }
+}
@@ -0,0 +1,226 @@
+//! Mod for reading and writing electorates in the PrefLib format (https://www.preflib.org), so
+//! that real-world ballot data can be run through the simulator and simulated electorates can be
+//! used in other tools. Only strict orders (the soc and soi data types) are supported, since
+//! RealOrdinalVoter does not permit equalities. Candidates are numbered from 1 in PrefLib files
+//! and from 0 in CandidateIDs.
+
+use crate::election::{CandidateID, OrdinalEnum, RealOrdinalVoter, Voter};
+use std::collections::HashMap;
+use std::io;
+use std::io::{BufRead, Write};
+
+/// Write the ordinal ballots the voters cast (as for plurality, since a file holds a single
+/// ballot per voter) to writer in the PrefLib ORDERING format. Identical ballots are grouped
+/// together with their multiplicity, most common first. The data type is soc if every ballot
+/// ranks all num_candidates candidates, and soi otherwise. An empty ballot (i.e. an indifferent
+/// HonestVoter abstaining) is written as a count with no ranking.
+pub fn write_preflib<T: Voter>(
+    voters: &mut Vec<T>,
+    num_candidates: usize,
+    mut writer: impl Write,
+) -> io::Result<()> {
+    // Group identical ballots, in order of first appearance
+    let mut orders: Vec<(Vec<CandidateID>, usize)> = Vec::new();
+    let mut indices: HashMap<Vec<CandidateID>, usize> = HashMap::new();
+    for voter in voters.iter_mut() {
+        let ballot = voter.cast_ordinal_ballot(OrdinalEnum::plurality);
+        match indices.get(ballot) {
+            Some(&i) => orders[i].1 += 1,
+            None => {
+                indices.insert(ballot.clone(), orders.len());
+                orders.push((ballot.clone(), 1));
+            }
+        }
+    }
+    orders.sort_by(|(_, a), (_, b)| b.cmp(a));
+
+    let complete = orders
+        .iter()
+        .all(|(ballot, _)| ballot.len() == num_candidates);
+    writeln!(
+        writer,
+        "# DATA TYPE: {}",
+        if complete { "soc" } else { "soi" }
+    )?;
+    writeln!(writer, "# MODIFICATION TYPE: synthetic")?;
+    writeln!(writer, "# NUMBER ALTERNATIVES: {}", num_candidates)?;
+    writeln!(writer, "# NUMBER VOTERS: {}", voters.len())?;
+    writeln!(writer, "# NUMBER UNIQUE ORDERS: {}", orders.len())?;
+    for i in 1..=num_candidates {
+        writeln!(writer, "# ALTERNATIVE NAME {}: Candidate {}", i, i)?;
+    }
+    for (ballot, count) in orders {
+        let ranking = ballot
+            .iter()
+            .map(|&CandidateID(id)| (id + 1).to_string())
+            .collect::<Vec<_>>()
+            .join(",");
+        writeln!(writer, "{}: {}", count, ranking)?;
+    }
+    Ok(())
+}
+
+/// Read an electorate written in the PrefLib ORDERING format (soc or soi), returning the number of
+/// candidates from the NUMBER ALTERNATIVES header along with one RealOrdinalVoter per voter.
+/// Truncated (soi) rankings are kept as they are, so candidates a voter didn't rank are left off
+/// their ballot rather than tied last, and an order ranking no candidates is read as an abstention
+/// with an empty ballot. Returns an InvalidData error if the file is malformed, ranks candidates
+/// equally, ranks a candidate twice, or references a candidate outside the election.
+pub fn read_preflib(reader: impl BufRead) -> io::Result<(usize, Vec<RealOrdinalVoter>)> {
+    fn invalid(message: String) -> io::Error {
+        io::Error::new(io::ErrorKind::InvalidData, message)
+    }
+
+    let mut num_candidates = None;
+    let mut voters = Vec::new();
+    for line in reader.lines() {
+        let line = line?;
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+        if let Some(header) = line.strip_prefix('#') {
+            if let Some(value) = header.trim().strip_prefix("NUMBER ALTERNATIVES:") {
+                num_candidates = Some(
+                    value
+                        .trim()
+                        .parse::<usize>()
+                        .map_err(|e| invalid(format!("bad NUMBER ALTERNATIVES: {}", e)))?,
+                );
+            }
+            continue;
+        }
+
+        let n = num_candidates
+            .ok_or_else(|| invalid("missing NUMBER ALTERNATIVES header".to_string()))?;
+        let (count, ranking) = line
+            .split_once(':')
+            .ok_or_else(|| invalid(format!("expected \"count: ranking\", found {:?}", line)))?;
+        if ranking.contains('{') {
+            return Err(invalid(format!(
+                "ranked equalities are not supported: {:?}",
+                line
+            )));
+        }
+        let count = count
+            .trim()
+            .parse::<usize>()
+            .map_err(|e| invalid(format!("bad count in {:?}: {}", line, e)))?;
+        let ballot = ranking
+            .split(',')
+            .filter(|_| !ranking.trim().is_empty())
+            .map(|c| match c.trim().parse::<usize>() {
+                Ok(c) if (1..=n).contains(&c) => Ok(CandidateID(c - 1)),
+                _ => Err(invalid(format!("bad candidate {:?} in {:?}", c, line))),
+            })
+            .collect::<io::Result<Vec<_>>>()?;
+        if (1..ballot.len()).any(|i| ballot[..i].contains(&ballot[i])) {
+            return Err(invalid(format!("candidate ranked twice in {:?}", line)));
+        }
+        (0..count).for_each(|_| voters.push(RealOrdinalVoter::new(ballot.clone())));
+    }
+
+    let n =
+        num_candidates.ok_or_else(|| invalid("missing NUMBER ALTERNATIVES header".to_string()))?;
+    Ok((n, voters))
+}
+
+/// Unit tests for this module
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::election::ApprovalThresholdBehavior::Mean;
+    use crate::election::{ElectionMethods, HonestVoter};
+    use crate::utility_functions::higher_index_wins;
+    use crate::utility_generators::uniform_utilities;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    #[test]
+    fn test_preflib_round_trip() {
+        let mut rng = StdRng::seed_from_u64(683);
+        let mut voters = (0..50)
+            .map(|_| HonestVoter::new(uniform_utilities(&mut rng, 4), false, Mean))
+            .collect::<Vec<_>>();
+        let mut file = Vec::new();
+        write_preflib(&mut voters, 4, &mut file).unwrap();
+
+        let (num_candidates, mut parsed) = read_preflib(&file[..]).unwrap();
+        assert_eq!(num_candidates, 4);
+        assert_eq!(parsed.len(), 50);
+        assert_eq!(
+            ElectionMethods::plurality(&mut parsed, 4, higher_index_wins()),
+            ElectionMethods::plurality(&mut voters, 4, higher_index_wins())
+        );
+    }
+
+    #[test]
+    fn test_write_preflib_groups_ballots() {
+        let mut voters = vec![
+            RealOrdinalVoter::new(vec![CandidateID(1), CandidateID(0)]),
+            RealOrdinalVoter::new(vec![CandidateID(0), CandidateID(1)]),
+            RealOrdinalVoter::new(vec![CandidateID(0), CandidateID(1)]),
+        ];
+        let mut file = Vec::new();
+        write_preflib(&mut voters, 2, &mut file).unwrap();
+        let file = String::from_utf8(file).unwrap();
+        assert!(file.contains("# DATA TYPE: soc\n"));
+        assert!(file.contains("# NUMBER UNIQUE ORDERS: 2\n"));
+        assert!(file.ends_with("2: 1,2\n1: 2,1\n"));
+    }
+
+    #[test]
+    fn test_read_preflib_truncated() {
+        let file = "# DATA TYPE: soi\n\
+                    # NUMBER ALTERNATIVES: 4\n\
+                    # NUMBER VOTERS: 10\n\
+                    # NUMBER UNIQUE ORDERS: 4\n\
+                    4: 1\n\
+                    3: 2,3\n\
+                    2: 3,2,1\n\
+                    1: 4,2\n";
+        let (num_candidates, mut voters) = read_preflib(file.as_bytes()).unwrap();
+        assert_eq!(num_candidates, 4);
+        assert_eq!(voters.len(), 10);
+        assert_eq!(voters[0].ballot(), &vec![CandidateID(0)]);
+        assert_eq!(voters[9].ballot(), &vec![CandidateID(3), CandidateID(1)]);
+
+        // Unranked candidates get no support: after C and D are eliminated, B leads A 6 to 4, since
+        // the ballots ranking only A never count towards anyone else
+        assert_eq!(
+            ElectionMethods::plurality(&mut voters, 4, higher_index_wins())[0],
+            CandidateID(0)
+        );
+        assert_eq!(
+            ElectionMethods::irv(&mut voters, 4, higher_index_wins()),
+            [1, 0, 2, 3].map(CandidateID).to_vec()
+        );
+    }
+
+    #[test]
+    fn test_preflib_round_trip_abstention() {
+        let mut voters = vec![
+            HonestVoter::new(vec![0.5; 3], false, Mean),
+            HonestVoter::new(vec![0.0, 1.0, 0.5], false, Mean),
+        ];
+        let mut file = Vec::new();
+        write_preflib(&mut voters, 3, &mut file).unwrap();
+        assert!(String::from_utf8(file.clone())
+            .unwrap()
+            .contains("# DATA TYPE: soi\n"));
+
+        let (num_candidates, parsed) = read_preflib(&file[..]).unwrap();
+        assert_eq!(num_candidates, 3);
+        assert_eq!(parsed.len(), 2);
+        assert!(parsed.iter().any(|voter| voter.ballot().is_empty()));
+    }
+
+    #[test]
+    fn test_read_preflib_rejects_ties() {
+        let file = "# NUMBER ALTERNATIVES: 3\n2: 1,{2,3}\n";
+        assert_eq!(
+            read_preflib(file.as_bytes()).unwrap_err().kind(),
+            io::ErrorKind::InvalidData
+        );
+    }
+}
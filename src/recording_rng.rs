@@ -0,0 +1,134 @@
+//! Mod for RNG wrappers which record and replay random draws, so that a simulation producing a
+//! surprising outcome can be reproduced exactly (i.e. to rebuild the same voters) without knowing
+//! how the original RNG was seeded. Since the generators take any Rng, these plug in directly.
+
+use rand::{Error, RngCore};
+
+const REPLAY_WARNING: &str = "ReplayRng has run out of recorded draws!";
+
+/// Fill dest with bytes taken from successive next_u64 draws of rng, in little-endian order. Any
+/// unused bytes of the final draw are discarded.
+fn fill_bytes_from_u64s<R: RngCore>(rng: &mut R, dest: &mut [u8]) {
+    for chunk in dest.chunks_mut(8) {
+        let bytes = rng.next_u64().to_le_bytes();
+        chunk.copy_from_slice(&bytes[..chunk.len()]);
+    }
+}
+
+/// Wraps an RNG, logging every draw made from it. All randomness is drawn from the wrapped RNG as
+/// u64s (next_u32 takes the low half of a u64 draw), so the log is exactly what a ReplayRng needs
+/// to reproduce the same sequence of values.
+#[derive(Debug, Clone)]
+pub struct RecordingRng<R: RngCore> {
+    rng: R,
+    draws: Vec<u64>,
+}
+
+impl<R: RngCore> RecordingRng<R> {
+    pub fn new(rng: R) -> Self {
+        Self {
+            rng,
+            draws: Vec::new(),
+        }
+    }
+
+    /// Returns the draws made so far, in order
+    pub fn draws(&self) -> &Vec<u64> {
+        &self.draws
+    }
+
+    /// Consumes the RecordingRng, returning the draws made from it
+    pub fn into_draws(self) -> Vec<u64> {
+        self.draws
+    }
+}
+
+impl<R: RngCore> RngCore for RecordingRng<R> {
+    fn next_u32(&mut self) -> u32 {
+        self.next_u64() as u32
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        let draw = self.rng.next_u64();
+        self.draws.push(draw);
+        draw
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        fill_bytes_from_u64s(self, dest)
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), Error> {
+        self.fill_bytes(dest);
+        Ok(())
+    }
+}
+
+/// An RNG which replays draws recorded by a RecordingRng. Making the same sequence of calls as
+/// were made on the RecordingRng produces the same values. Panics if more draws are made than
+/// were recorded.
+#[derive(Debug, Clone)]
+pub struct ReplayRng {
+    draws: Vec<u64>,
+    next: usize,
+}
+
+impl ReplayRng {
+    pub fn new(draws: Vec<u64>) -> Self {
+        Self { draws, next: 0 }
+    }
+
+    /// Returns the number of recorded draws not yet replayed
+    pub fn remaining(&self) -> usize {
+        self.draws.len() - self.next
+    }
+}
+
+impl RngCore for ReplayRng {
+    fn next_u32(&mut self) -> u32 {
+        self.next_u64() as u32
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        let draw = *self
+            .draws
+            .get(self.next)
+            .unwrap_or_else(|| panic!("{}", REPLAY_WARNING));
+        self.next += 1;
+        draw
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        fill_bytes_from_u64s(self, dest)
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), Error> {
+        self.fill_bytes(dest);
+        Ok(())
+    }
+}
+
+/// Unit tests for this module
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utility_generators::{beta_utility_matrix, uniform_utility_matrix};
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
+    use rand_distr::Beta;
+
+    #[test]
+    fn test_record_and_replay() {
+        let beta = Beta::new(0.5, 0.5).unwrap();
+        let mut recording = RecordingRng::new(StdRng::seed_from_u64(698));
+        let uniform = uniform_utility_matrix(&mut recording, 10, 4);
+        let skewed = beta_utility_matrix(beta, &mut recording, 10, 4);
+        let coin = recording.gen_bool(0.5);
+
+        let mut replay = ReplayRng::new(recording.into_draws());
+        assert_eq!(uniform_utility_matrix(&mut replay, 10, 4), uniform);
+        assert_eq!(beta_utility_matrix(beta, &mut replay, 10, 4), skewed);
+        assert_eq!(replay.gen_bool(0.5), coin);
+        assert_eq!(replay.remaining(), 0);
+    }
+}
@@ -0,0 +1,191 @@
+//! Mod for the TieBreaker trait, which lets tie-breakers carry state (i.e. a random order or a
+//! table of utilities) as structs rather than closures. Election methods take a plain
+//! Fn(&usize, &usize) -> Ordering + Copy tie-breaker, and tie_breaker_fn adapts any TieBreaker to
+//! one, so every method can be run with any TieBreaker. Each election method with the standard
+//! signature also has a parallel variant taking a TieBreaker directly (i.e.
+//! ElectionMethods::plurality_with_tie_breaker).
+
+use crate::election::{CandidateID, ElectionMethods, Voter};
+use rand::seq::SliceRandom;
+use rand::Rng;
+use std::cmp::Ordering;
+
+/// Trait for tie-breakers. As for tie-breaker closures, break_tie(a, b) is called with candidate
+/// indices and returns Ordering::Greater if a should finish ahead of b. It must be a consistent
+/// total order, and must never return Ordering::Equal for two different candidates.
+pub trait TieBreaker {
+    fn break_tie(&self, a: usize, b: usize) -> Ordering;
+}
+
+/// Adapt a TieBreaker to the tie-breaker closure election methods take. Since the closure only
+/// borrows the TieBreaker, it is Copy regardless of what state the TieBreaker holds.
+pub fn tie_breaker_fn<B: TieBreaker>(
+    tie_breaker: &B,
+) -> impl Fn(&usize, &usize) -> Ordering + Copy + '_ {
+    move |&a: &usize, &b: &usize| tie_breaker.break_tie(a, b)
+}
+
+/// Generates, for each listed election method, a variant which takes a TieBreaker in place of a
+/// tie-breaker closure
+macro_rules! tie_breaker_variants {
+    ($($method:ident => $variant:ident),* $(,)?) => {
+        impl ElectionMethods {
+            $(
+                #[doc = concat!(stringify!($method), ", breaking ties with a TieBreaker")]
+                pub fn $variant<T: Voter, B: TieBreaker>(
+                    voters: &mut Vec<T>,
+                    num_candidates: usize,
+                    tie_breaker: &B,
+                ) -> Vec<CandidateID> {
+                    ElectionMethods::$method(voters, num_candidates, tie_breaker_fn(tie_breaker))
+                }
+            )*
+        }
+    };
+}
+
+tie_breaker_variants!(
+    plurality => plurality_with_tie_breaker,
+    fptp_runoff => fptp_runoff_with_tie_breaker,
+    two_round_system => two_round_system_with_tie_breaker,
+    contingent_vote => contingent_vote_with_tie_breaker,
+    irv => irv_with_tie_breaker,
+    borda => borda_with_tie_breaker,
+    schulze => schulze_with_tie_breaker,
+    tideman_alternative => tideman_alternative_with_tie_breaker,
+    plurality_smith => plurality_smith_with_tie_breaker,
+    coombs_equal_rankings => coombs_equal_rankings_with_tie_breaker,
+    baldwin => baldwin_with_tie_breaker,
+    average_rank_elimination => average_rank_elimination_with_tie_breaker,
+    approval => approval_with_tie_breaker,
+    utility_weighted_approval => utility_weighted_approval_with_tie_breaker,
+    approval_runoff => approval_runoff_with_tie_breaker,
+    score_5 => score_5_with_tie_breaker,
+    score_10 => score_10_with_tie_breaker,
+    score_100 => score_100_with_tie_breaker,
+    score_5_runoff => score_5_runoff_with_tie_breaker,
+    score_10_runoff => score_10_runoff_with_tie_breaker,
+    score_100_runoff => score_100_runoff_with_tie_breaker,
+    star_5 => star_5_with_tie_breaker,
+    star_10 => star_10_with_tie_breaker,
+    star_100 => star_100_with_tie_breaker,
+);
+
+/// Wraps a tie-breaker closure or function (i.e. higher_index_wins()) as a TieBreaker
+#[derive(Debug, Copy, Clone)]
+pub struct FnTieBreaker<F: Fn(&usize, &usize) -> Ordering>(pub F);
+
+impl<F: Fn(&usize, &usize) -> Ordering> TieBreaker for FnTieBreaker<F> {
+    fn break_tie(&self, a: usize, b: usize) -> Ordering {
+        (self.0)(&a, &b)
+    }
+}
+
+/// Breaks ties by a random order of the candidates, drawn once when the RandomTieBreaker is
+/// created, so that every tie in an election is broken consistently.
+#[derive(Debug, Clone)]
+pub struct RandomTieBreaker {
+    /// priorities\[i] is the priority of CandidateID(i); the higher priority finishes ahead
+    priorities: Vec<usize>,
+}
+
+impl RandomTieBreaker {
+    /// Draw a random order of num_candidates candidates
+    pub fn new<R: Rng>(rng: &mut R, num_candidates: usize) -> Self {
+        let mut priorities = (0..num_candidates).collect::<Vec<_>>();
+        priorities.shuffle(rng);
+        Self { priorities }
+    }
+}
+
+impl TieBreaker for RandomTieBreaker {
+    fn break_tie(&self, a: usize, b: usize) -> Ordering {
+        self.priorities[a].cmp(&self.priorities[b])
+    }
+}
+
+/// Breaks ties in favor of the candidate with the greater total utility across the voters it was
+/// built from, falling back to the higher index if their totals are equal.
+#[derive(Debug, Clone)]
+pub struct UtilityTieBreaker {
+    /// totals\[i] is the total utility of CandidateID(i)
+    totals: Vec<f64>,
+}
+
+impl UtilityTieBreaker {
+    /// Total the voters' utilities. Requires that the voters contain utility information.
+    pub fn new<T: Voter>(voters: &Vec<T>, num_candidates: usize) -> Self {
+        let mut totals = vec![0f64; num_candidates];
+        for voter in voters {
+            voter
+                .utilities()
+                .iter()
+                .zip(totals.iter_mut())
+                .for_each(|(&utility, total)| *total += utility);
+        }
+        Self { totals }
+    }
+}
+
+impl TieBreaker for UtilityTieBreaker {
+    fn break_tie(&self, a: usize, b: usize) -> Ordering {
+        self.totals[a]
+            .partial_cmp(&self.totals[b])
+            .unwrap()
+            .then(a.cmp(&b))
+    }
+}
+
+/// Unit tests for this module
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::election::{ApprovalThresholdBehavior, HonestVoter};
+    use crate::utility_functions::lower_index_wins;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    #[test]
+    fn test_plurality_with_each_tie_breaker() {
+        // A and B tie with one first preference each, but B has the greater total utility
+        let voters = vec![
+            HonestVoter::new(vec![1.0, 0.3, 0.0], false, ApprovalThresholdBehavior::Mean),
+            HonestVoter::new(vec![0.1, 1.0, 0.0], false, ApprovalThresholdBehavior::Mean),
+        ];
+        fn plurality<B: TieBreaker>(
+            voters: &Vec<HonestVoter>,
+            tie_breaker: &B,
+        ) -> Vec<CandidateID> {
+            let ranking =
+                ElectionMethods::plurality_with_tie_breaker(&mut voters.clone(), 3, tie_breaker);
+            assert_eq!(
+                ranking,
+                ElectionMethods::plurality(&mut voters.clone(), 3, tie_breaker_fn(tie_breaker))
+            );
+            ranking
+        }
+
+        let wrapped = FnTieBreaker(lower_index_wins());
+        assert_eq!(
+            plurality(&voters, &wrapped),
+            vec![CandidateID(0), CandidateID(1), CandidateID(2)]
+        );
+
+        let by_utility = UtilityTieBreaker::new(&voters, 3);
+        assert_eq!(
+            plurality(&voters, &by_utility),
+            vec![CandidateID(1), CandidateID(0), CandidateID(2)]
+        );
+
+        let random = RandomTieBreaker::new(&mut StdRng::seed_from_u64(740), 3);
+        let winner = match random.break_tie(0, 1) {
+            Ordering::Greater => CandidateID(0),
+            _ => CandidateID(1),
+        };
+        assert_eq!(plurality(&voters, &random)[0], winner);
+        for (a, b) in [(0, 1), (0, 2), (1, 2)] {
+            assert_ne!(random.break_tie(a, b), Ordering::Equal);
+            assert_eq!(random.break_tie(a, b), random.break_tie(b, a).reverse());
+        }
+    }
+}
@@ -3,6 +3,7 @@
 //! mod.
 
 use std::cmp::Ordering;
+use std::collections::HashSet;
 
 use crate::election::CandidateID;
 
@@ -13,6 +14,22 @@ pub fn generate_candidates(n: usize) -> Vec<CandidateID> {
         .collect()
 }
 
+/// Tie-breaker factory: returns a tie-breaker under which, whenever two candidates are tied, the
+/// candidate with the lower index is ranked ahead.
+///
+/// By convention across this crate, a tie-breaker is called as tie_breaker(&a, &b) with candidate
+/// indices and returns Ordering::Greater if a should finish ahead of b. Note that this means
+/// passing a bare usize::cmp favors the *higher* index, which is easy to get backwards.
+pub fn lower_index_wins() -> impl Fn(&usize, &usize) -> Ordering + Copy {
+    |a: &usize, b: &usize| b.cmp(a)
+}
+
+/// Tie-breaker factory: returns a tie-breaker under which, whenever two candidates are tied, the
+/// candidate with the higher index is ranked ahead. Equivalent to passing usize::cmp directly.
+pub fn higher_index_wins() -> impl Fn(&usize, &usize) -> Ordering + Copy {
+    |a: &usize, b: &usize| a.cmp(b)
+}
+
 /// Helper function: given a vector of candidates and a vector of some quantity of the same length,
 /// sorts the vector of candidates in decreasing order by the corresponding field in the quantity
 /// vector (that is, Candidate(x) is sorted by key v\[x] descending) with a passed-in tie breaker.
@@ -26,6 +43,31 @@ pub fn sort_candidates_by_vec<T: PartialOrd, F: Fn(&usize, &usize) -> Ordering +
     });
 }
 
+/// Helper function: return the top n candidates by the corresponding field in v (that is,
+/// Candidate(x) is keyed by v\[x]) in decreasing order, with ties broken as in
+/// sort_candidates_by_vec. This uses a partial selection rather than sorting every candidate, so it
+/// is cheaper when only a few candidates are needed (i.e. runoff finalists). If n exceeds the
+/// number of candidates, every candidate is returned.
+pub fn top_n_by_vec<T: PartialOrd, F: Fn(&usize, &usize) -> Ordering + Copy>(
+    v: &Vec<T>,
+    n: usize,
+    tie_breaker: F,
+) -> Vec<CandidateID> {
+    let compare = |&CandidateID(a): &CandidateID, &CandidateID(b): &CandidateID| {
+        v[b].partial_cmp(&v[a]).unwrap().then(tie_breaker(&b, &a))
+    };
+    if n == 0 {
+        return Vec::new();
+    }
+    let mut candidates = generate_candidates(v.len());
+    if n < candidates.len() {
+        candidates.select_nth_unstable_by(n - 1, compare);
+        candidates.truncate(n);
+    }
+    candidates.sort_unstable_by(compare);
+    candidates
+}
+
 /// Helper function to scale utilities linearly so the min is 0 and max is 1, provided min != max
 pub fn scale_utilities_linearly(utilities: &Vec<f64>) -> Vec<f64> {
     let max = utilities
@@ -51,6 +93,17 @@ pub fn scale_utilities_linearly(utilities: &Vec<f64>) -> Vec<f64> {
         .collect()
 }
 
+/// Helper function to scale utilities linearly as in scale_utilities_linearly, then raise each to
+/// the given exponent. An exponent above 1 is convex (only candidates near the voter's favorite
+/// keep high utility), while an exponent below 1 is concave (only candidates near the voter's least
+/// favorite keep low utility).
+pub fn scale_utilities_power(utilities: &Vec<f64>, exponent: f64) -> Vec<f64> {
+    scale_utilities_linearly(utilities)
+        .into_iter()
+        .map(|f| f.powf(exponent).clamp(0f64, 1f64))
+        .collect()
+}
+
 /// Helper function to generate approval ballots based on a set bound:
 pub fn generate_approval_ballot(utilities: &Vec<f64>, bound: f64) -> Vec<CandidateID> {
     let mut ballot: Vec<CandidateID> = (0..(utilities.len()))
@@ -72,17 +125,111 @@ pub fn generate_approval_ballot(utilities: &Vec<f64>, bound: f64) -> Vec<Candida
     ballot
 }
 
+/// Helper function: whether two sets of winners (i.e. seats filled by a multi-winner method) contain
+/// the same candidates, ignoring order.
+pub fn same_winner_set(a: &[CandidateID], b: &[CandidateID]) -> bool {
+    let a = a.iter().map(|&CandidateID(id)| id).collect::<HashSet<_>>();
+    let b = b.iter().map(|&CandidateID(id)| id).collect::<HashSet<_>>();
+    a == b
+}
+
+/// Helper function: the Jaccard overlap of two sets of winners, that is the size of their
+/// intersection divided by the size of their union. Returns 1.0 if both sets are empty.
+pub fn jaccard_overlap(a: &[CandidateID], b: &[CandidateID]) -> f64 {
+    let a = a.iter().map(|&CandidateID(id)| id).collect::<HashSet<_>>();
+    let b = b.iter().map(|&CandidateID(id)| id).collect::<HashSet<_>>();
+    let union = a.union(&b).count();
+    if union == 0 {
+        1f64
+    } else {
+        a.intersection(&b).count() as f64 / union as f64
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::election::CandidateID;
-    use crate::utility_functions::{generate_candidates, sort_candidates_by_vec};
+    use crate::utility_functions::*;
+
+    #[test]
+    fn test_top_n_by_vec() {
+        fn matches_full_sort<F: Fn(&usize, &usize) -> Ordering + Copy>(
+            key: &Vec<usize>,
+            tie_breaker: F,
+        ) {
+            let mut sorted = generate_candidates(key.len());
+            sort_candidates_by_vec(&mut sorted, key, tie_breaker);
+            assert_eq!(top_n_by_vec(key, 2, tie_breaker), sorted[..2].to_vec());
+            assert_eq!(top_n_by_vec(key, key.len(), tie_breaker), sorted);
+        }
+
+        let key = vec![3, 9, 1, 9, 4, 7, 3];
+        matches_full_sort(&key, higher_index_wins());
+        matches_full_sort(&key, lower_index_wins());
+        assert!(top_n_by_vec(&key, 0, higher_index_wins()).is_empty());
+        assert_eq!(top_n_by_vec(&key, 10, higher_index_wins()).len(), 7);
+    }
+
+    #[test]
+    fn test_scale_utilities_power() {
+        let utilities = vec![0.2, 0.6, 0.4, 1.0];
+        assert_eq!(
+            scale_utilities_power(&utilities, 1.0),
+            scale_utilities_linearly(&utilities)
+        );
+        let squared = scale_utilities_power(&utilities, 2.0);
+        let expected = [0.0, 0.25, 0.0625, 1.0];
+        assert!(squared
+            .iter()
+            .zip(expected)
+            .all(|(&actual, expected)| (actual - expected).abs() < 1e-12));
+    }
 
     #[test]
     fn test_sort_candidates_by_vec() {
         let mut v = generate_candidates(3);
         let key = vec![20, 50, 10];
-        sort_candidates_by_vec(&mut v, &key, usize::cmp);
+        sort_candidates_by_vec(&mut v, &key, higher_index_wins());
         assert_eq!(v, vec![CandidateID(1), CandidateID(0), CandidateID(2)])
     }
-}
 
+    #[test]
+    fn test_lower_index_wins() {
+        let mut v = generate_candidates(3);
+        let key = vec![10, 50, 50];
+        sort_candidates_by_vec(&mut v, &key, lower_index_wins());
+        assert_eq!(v, vec![CandidateID(1), CandidateID(2), CandidateID(0)])
+    }
+
+    #[test]
+    fn test_higher_index_wins() {
+        let mut v = generate_candidates(3);
+        let key = vec![10, 50, 50];
+        sort_candidates_by_vec(&mut v, &key, higher_index_wins());
+        assert_eq!(v, vec![CandidateID(2), CandidateID(1), CandidateID(0)])
+    }
+
+    #[test]
+    fn test_winner_set_identical() {
+        let a = vec![CandidateID(0), CandidateID(2), CandidateID(3)];
+        let b = vec![CandidateID(3), CandidateID(0), CandidateID(2)];
+        assert!(same_winner_set(&a, &b));
+        assert_eq!(jaccard_overlap(&a, &b), 1.0);
+    }
+
+    #[test]
+    fn test_winner_set_disjoint() {
+        let a = vec![CandidateID(0), CandidateID(1)];
+        let b = vec![CandidateID(2), CandidateID(3)];
+        assert!(!same_winner_set(&a, &b));
+        assert_eq!(jaccard_overlap(&a, &b), 0.0);
+    }
+
+    #[test]
+    fn test_winner_set_partial_overlap() {
+        let a = vec![CandidateID(0), CandidateID(1), CandidateID(2)];
+        let b = vec![CandidateID(2), CandidateID(1), CandidateID(3)];
+        assert!(!same_winner_set(&a, &b));
+        assert_eq!(jaccard_overlap(&a, &b), 0.5);
+    }
+}
@@ -1,8 +1,24 @@
 //! mod to hold the various utility generators. Most are for generating the utility vecs for voters
 //! based on candidates
 
+use crate::election::{ApprovalThresholdBehavior, CandidateID, HonestVoter};
+use rand::distributions::{Distribution, WeightedIndex};
 use rand::Rng;
-use rand_distr::Beta;
+use rand_distr::{Beta, Normal};
+
+const NORMAL_WARNING: &str =
+    "The standard deviation of a NormalModel must be finite and non-negative!";
+
+const HISTOGRAM_WARNING: &str =
+    "A utility histogram must have at least one bin, with non-negative, finite weights summing to \
+    more than 0!";
+
+const BANDWIDTH_WARNING: &str = "Smoothing bandwidth must be finite and positive!";
+
+const NOISE_WARNING: &str = "Utility noise must have a finite, non-negative standard deviation!";
+
+const SPATIAL_WARNING: &str =
+    "A SpatialModel must be asked for exactly as many candidates as it has locations for!";
 
 /// Given a number of candidates n, generate a utility vector of n elements where the utilities are
 /// chosen at random from the uniform distribution over [0, 1].
@@ -17,6 +33,100 @@ pub fn beta_utilities<T: Rng>(beta: Beta<f64>, rng: &mut T, n: usize) -> Vec<f64
     (0..n).map(|_| rng.sample(beta)).collect()
 }
 
+/// Given a number of candidates n, generate a utility vector of n elements for a voter belonging to
+/// one of two opposing camps, chosen at random with equal probability. The first camp likes
+/// CandidateID(0) through CandidateID(n / 2 - 1) and dislikes the rest, and the second camp the
+/// reverse. Liked candidates' utilities are chosen uniformly from [0.8, 1], and disliked
+/// candidates' from [0, 0.2], so electorates drawn this way are sharply polarized.
+pub fn polarized_utilities<T: Rng>(rng: &mut T, n: usize) -> Vec<f64> {
+    let first_camp = rng.gen_bool(0.5);
+    (0..n)
+        .map(|i| {
+            if (i < n / 2) == first_camp {
+                rng.gen_range(0.8..=1.0)
+            } else {
+                rng.gen_range(0.0..=0.2)
+            }
+        })
+        .collect()
+}
+
+/// Given a number of candidates n, generate a utility vector of n elements where the utilities are
+/// chosen at random from the uniform distribution over [0, 1], except that boost is added to the
+/// utility of CandidateID(frontrunner) before clamping back into [0, 1]. Electorates drawn this
+/// way systematically favor the frontrunner.
+pub fn frontrunner_utilities<T: Rng>(
+    rng: &mut T,
+    n: usize,
+    frontrunner: usize,
+    boost: f64,
+) -> Vec<f64> {
+    let mut utilities = uniform_utilities(rng, n);
+    utilities[frontrunner] = (utilities[frontrunner] + boost).clamp(0f64, 1f64);
+    utilities
+}
+
+/// Given a number of candidates n, generate a utility vector of n elements drawn from an empirical
+/// distribution: [0, 1] is split into histogram.len() equal-width bins, where histogram\[i] is the
+/// probability of bin i (the weights need not sum to 1, and are normalized), and each utility is
+/// chosen uniformly from within a bin chosen with those probabilities.
+pub fn utilities_from_histogram<R: Rng>(rng: &mut R, histogram: &[f64], n: usize) -> Vec<f64> {
+    let bins = WeightedIndex::new(histogram).unwrap_or_else(|_| panic!("{}", HISTOGRAM_WARNING));
+    let width = 1f64 / histogram.len() as f64;
+    (0..n)
+        .map(|_| {
+            let bin = bins.sample(rng) as f64;
+            (bin + rng.gen::<f64>()) * width
+        })
+        .collect()
+}
+
+/// Given a voter's utility vector, return it extended with their utility for a clone of
+/// CandidateID(original): a near-duplicate candidate, who becomes the last CandidateID. The clone's
+/// utility is the original's plus noise chosen uniformly from [-similarity, similarity], clamped
+/// back into [0, 1]. Applying this to every voter in an electorate builds an electorate for
+/// testing independence of clones.
+pub fn add_clone<T: Rng>(
+    rng: &mut T,
+    utilities: &Vec<f64>,
+    original: CandidateID,
+    similarity: f64,
+) -> Vec<f64> {
+    let offset = if similarity > 0f64 {
+        rng.gen_range(-similarity..=similarity)
+    } else {
+        0f64
+    };
+    let mut extended = utilities.clone();
+    extended.push((utilities[original.0] + offset).clamp(0f64, 1f64));
+    extended
+}
+
+/// Generate the utilities of an entire electorate at once: a num_voters x num_candidates matrix
+/// where each row is a voter's utility vector, drawn as in uniform_utilities.
+pub fn uniform_utility_matrix<T: Rng>(
+    rng: &mut T,
+    num_voters: usize,
+    num_candidates: usize,
+) -> Vec<Vec<f64>> {
+    (0..num_voters)
+        .map(|_| uniform_utilities(rng, num_candidates))
+        .collect()
+}
+
+/// Generate the utilities of an entire electorate at once: a num_voters x num_candidates matrix
+/// where each row is a voter's utility vector, drawn as in beta_utilities.
+pub fn beta_utility_matrix<T: Rng>(
+    beta: Beta<f64>,
+    rng: &mut T,
+    num_voters: usize,
+    num_candidates: usize,
+) -> Vec<Vec<f64>> {
+    (0..num_voters)
+        .map(|_| beta_utilities(beta, rng, num_candidates))
+        .collect()
+}
+
 /// Given a list of n candidates location's in k-dimensional space and this voter's location in the
 /// same k-dimensional space, calculate the voter's utility vector for each candidate based upon
 /// the specified metric. In this case, we will define the assigned utility of a voter for candidate
@@ -35,3 +145,304 @@ pub fn distance_utilities<Metric: Fn(&Vec<f64>, &Vec<f64>) -> f64>(
         })
         .collect()
 }
+
+/// As distance_utilities, but each candidate also has a valence: an intrinsic quality which makes
+/// them more (or, if negative, less) appealing to every voter regardless of position.
+/// valence\[i] is added to the voter's utility for CandidateID(i) after the 1 / (1 + metric)
+/// transform, and the result is clamped back into [0, 1].
+pub fn distance_utilities_with_valence<Metric: Fn(&Vec<f64>, &Vec<f64>) -> f64>(
+    candidate_locations: &Vec<Vec<f64>>,
+    voter_location: &Vec<f64>,
+    valence: &Vec<f64>,
+    m: Metric,
+) -> Vec<f64> {
+    distance_utilities(candidate_locations, voter_location, m)
+        .into_iter()
+        .zip(valence)
+        .map(|(utility, &v)| (utility + v).clamp(0f64, 1f64))
+        .collect()
+}
+
+/// As distance_utilities, but with idiosyncratic preferences beyond ideology: normally distributed
+/// noise with standard deviation noise_std is added to the voter's utility for each candidate, and
+/// the result is clamped back into [0, 1].
+pub fn noisy_spatial_utilities<R: Rng, Metric: Fn(&Vec<f64>, &Vec<f64>) -> f64>(
+    rng: &mut R,
+    candidate_locations: &Vec<Vec<f64>>,
+    voter_location: &Vec<f64>,
+    metric: Metric,
+    noise_std: f64,
+) -> Vec<f64> {
+    let noise = Normal::new(0f64, noise_std).unwrap_or_else(|_| panic!("{}", NOISE_WARNING));
+    distance_utilities(candidate_locations, voter_location, metric)
+        .into_iter()
+        .map(|utility| (utility + rng.sample(noise)).clamp(0f64, 1f64))
+        .collect()
+}
+
+/// Smooth a voter's utilities over candidate similarity, to model sentiment spilling over between
+/// similar candidates. Each candidate's utility is replaced by the average of every candidate's
+/// raw utility (their own included), weighted by a Gaussian kernel exp(-d^2 / (2 * bandwidth^2))
+/// of the distance d between the two candidates as measured by the given metric. Here,
+/// candidate_locations\[i] is the location of CandidateID(i).
+pub fn smooth_utilities<Metric: Fn(&Vec<f64>, &Vec<f64>) -> f64>(
+    raw: &Vec<f64>,
+    candidate_locations: &Vec<Vec<f64>>,
+    bandwidth: f64,
+    metric: Metric,
+) -> Vec<f64> {
+    if !(bandwidth.is_finite() && bandwidth > 0f64) {
+        panic!("{}", BANDWIDTH_WARNING)
+    }
+    candidate_locations
+        .iter()
+        .map(|location| {
+            let (weighted_sum, total_weight) = candidate_locations.iter().zip(raw).fold(
+                (0f64, 0f64),
+                |(weighted_sum, total_weight), (other, &utility)| {
+                    let distance = metric(location, other);
+                    let weight = (-distance * distance / (2f64 * bandwidth * bandwidth)).exp();
+                    (weighted_sum + weight * utility, total_weight + weight)
+                },
+            );
+            weighted_sum / total_weight
+        })
+        .collect()
+}
+
+/// Trait for models of an electorate's utilities, so that simulation code can be written
+/// generically over how voters' utilities are generated. Every model generates utilities in [0, 1].
+pub trait UtilityModel {
+    /// Generate one voter's utility vector of num_candidates elements, where element i is their
+    /// utility for CandidateID(i).
+    fn generate<R: Rng>(&self, rng: &mut R, num_candidates: usize) -> Vec<f64>;
+}
+
+/// Utilities drawn as in uniform_utilities
+#[derive(Debug, Copy, Clone, Default)]
+pub struct UniformModel;
+
+impl UtilityModel for UniformModel {
+    fn generate<R: Rng>(&self, rng: &mut R, num_candidates: usize) -> Vec<f64> {
+        uniform_utilities(rng, num_candidates)
+    }
+}
+
+/// Utilities drawn as in beta_utilities, from the given Beta distribution
+#[derive(Debug, Copy, Clone)]
+pub struct BetaModel(pub Beta<f64>);
+
+impl UtilityModel for BetaModel {
+    fn generate<R: Rng>(&self, rng: &mut R, num_candidates: usize) -> Vec<f64> {
+        beta_utilities(self.0, rng, num_candidates)
+    }
+}
+
+/// Utilities drawn independently from a normal distribution, then clamped into [0, 1]
+#[derive(Debug, Copy, Clone)]
+pub struct NormalModel(Normal<f64>);
+
+impl NormalModel {
+    /// Create a model drawing utilities from the normal distribution with the given mean and
+    /// standard deviation. Panics if the standard deviation is negative or not finite.
+    pub fn new(mean: f64, std_dev: f64) -> Self {
+        Self(Normal::new(mean, std_dev).unwrap_or_else(|_| panic!("{}", NORMAL_WARNING)))
+    }
+}
+
+impl UtilityModel for NormalModel {
+    fn generate<R: Rng>(&self, rng: &mut R, num_candidates: usize) -> Vec<f64> {
+        (0..num_candidates)
+            .map(|_| rng.sample(self.0).clamp(0f64, 1f64))
+            .collect()
+    }
+}
+
+/// Utilities from the spatial model: each voter is placed by drawing their coordinate in dimension
+/// d from voter_distributions\[d], and their utilities are then computed from their distance to
+/// each of the candidate_locations as in distance_utilities.
+#[derive(Clone)]
+pub struct SpatialModel<D, Metric> {
+    pub candidate_locations: Vec<Vec<f64>>,
+    pub voter_distributions: Vec<D>,
+    pub metric: Metric,
+}
+
+impl<D, Metric> UtilityModel for SpatialModel<D, Metric>
+where
+    D: Distribution<f64>,
+    Metric: Fn(&Vec<f64>, &Vec<f64>) -> f64,
+{
+    /// Panics if num_candidates is not the number of candidate_locations
+    fn generate<R: Rng>(&self, rng: &mut R, num_candidates: usize) -> Vec<f64> {
+        if num_candidates != self.candidate_locations.len() {
+            panic!("{}", SPATIAL_WARNING)
+        }
+        let voter_location = self
+            .voter_distributions
+            .iter()
+            .map(|distribution| distribution.sample(rng))
+            .collect();
+        distance_utilities(&self.candidate_locations, &voter_location, &self.metric)
+    }
+}
+
+/// Generate the classic three-faction "rock-paper-scissors" electorate, which has a guaranteed
+/// Condorcet cycle: voters_per_faction honest voters each with preferences A > B > C, B > C > A,
+/// and C > A > B, where A, B, and C are CandidateID(0), CandidateID(1), and CandidateID(2).
+pub fn condorcet_cycle_electorate(voters_per_faction: usize) -> Vec<HonestVoter> {
+    let factions = [[1.0, 0.5, 0.0], [0.0, 1.0, 0.5], [0.5, 0.0, 1.0]];
+    factions
+        .iter()
+        .flat_map(|utilities| {
+            (0..voters_per_faction).map(move |_| {
+                HonestVoter::new(utilities.to_vec(), false, ApprovalThresholdBehavior::Mean)
+            })
+        })
+        .collect()
+}
+
+/// Unit tests for this module
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::metrics::lp_metric;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    #[test]
+    fn test_utilities_from_histogram() {
+        let histogram = [0.5, 0.0, 0.2, 0.3];
+        let utilities =
+            utilities_from_histogram(&mut StdRng::seed_from_u64(733), &histogram, 20000);
+        let mut counts = [0usize; 4];
+        utilities
+            .iter()
+            .for_each(|&utility| counts[((utility * 4.0) as usize).min(3)] += 1);
+        for (count, expected) in counts.iter().zip(histogram) {
+            assert!((*count as f64 / 20000.0 - expected).abs() < 0.02);
+        }
+        assert!(utilities.iter().all(|&u| (0f64..=1f64).contains(&u)));
+    }
+
+    #[test]
+    fn test_noisy_spatial_utilities() {
+        let mut rng = StdRng::seed_from_u64(727);
+        let candidate_locations = vec![vec![0.0, 0.0], vec![1.0, 2.0], vec![-3.0, 0.5]];
+        let voter_location = vec![0.5, 0.5];
+        let clean = distance_utilities(&candidate_locations, &voter_location, lp_metric::<2>);
+        let noiseless = noisy_spatial_utilities(
+            &mut rng,
+            &candidate_locations,
+            &voter_location,
+            lp_metric::<2>,
+            0.0,
+        );
+        assert_eq!(noiseless, clean);
+
+        let noisy = noisy_spatial_utilities(
+            &mut rng,
+            &candidate_locations,
+            &voter_location,
+            lp_metric::<2>,
+            0.3,
+        );
+        assert_ne!(noisy, clean);
+        assert!(noisy.iter().all(|&u| (0f64..=1f64).contains(&u)));
+    }
+
+    #[test]
+    fn test_smooth_utilities() {
+        // A and B are close together, while C is far from both
+        let locations = vec![vec![0.0], vec![0.1], vec![5.0]];
+        let raw = vec![1.0, 0.0, 0.5];
+        let smoothed = smooth_utilities(&raw, &locations, 0.5, lp_metric::<2>);
+        assert!((smoothed[0] - smoothed[1]).abs() < 0.05);
+        assert!((smoothed[0] - 0.5).abs() < 0.05);
+        assert!((smoothed[2] - raw[2]).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_frontrunner_utilities() {
+        let mut rng = StdRng::seed_from_u64(678);
+        let mut totals = vec![0f64; 4];
+        for _ in 0..1000 {
+            let utilities = frontrunner_utilities(&mut rng, 4, 2, 0.3);
+            assert!(utilities.iter().all(|&u| (0f64..=1f64).contains(&u)));
+            totals.iter_mut().zip(utilities).for_each(|(total, u)| *total += u);
+        }
+        assert!((0..4).filter(|&i| i != 2).all(|i| totals[2] > totals[i]));
+    }
+
+    #[test]
+    fn test_utility_models() {
+        fn check<M: UtilityModel>(model: M) {
+            let mut rng = StdRng::seed_from_u64(695);
+            for _ in 0..100 {
+                let utilities = model.generate(&mut rng, 3);
+                assert_eq!(utilities.len(), 3);
+                assert!(utilities.iter().all(|&u| (0f64..=1f64).contains(&u)));
+            }
+        }
+
+        check(UniformModel);
+        check(BetaModel(Beta::new(2.0, 2.0).unwrap()));
+        check(NormalModel::new(0.5, 0.5));
+        check(SpatialModel {
+            candidate_locations: vec![vec![-1.0, 0.0], vec![0.0, 0.0], vec![1.0, 1.0]],
+            voter_distributions: vec![Normal::new(0.0, 1.0).unwrap(); 2],
+            metric: lp_metric::<2>,
+        });
+    }
+
+    #[test]
+    fn test_distance_utilities_with_valence() {
+        let candidate_locations = vec![vec![0.0, 0.0], vec![1.0, 1.0], vec![-2.0, 1.0]];
+        let voter_locations = [vec![0.5, 0.0], vec![-1.0, 1.0], vec![3.0, -2.0]];
+        for voter_location in &voter_locations {
+            let plain = distance_utilities_with_valence(
+                &candidate_locations,
+                voter_location,
+                &vec![0.0; 3],
+                lp_metric::<2>,
+            );
+            let boosted = distance_utilities_with_valence(
+                &candidate_locations,
+                voter_location,
+                &vec![0.0, 0.2, 0.0],
+                lp_metric::<2>,
+            );
+            assert_eq!(
+                plain,
+                distance_utilities(&candidate_locations, voter_location, lp_metric::<2>)
+            );
+            assert!(boosted[1] > plain[1]);
+            assert_eq!((boosted[0], boosted[2]), (plain[0], plain[2]));
+        }
+    }
+
+    #[test]
+    fn test_add_clone() {
+        let mut rng = StdRng::seed_from_u64(705);
+        for _ in 0..100 {
+            let utilities = uniform_utilities(&mut rng, 3);
+            let cloned = add_clone(&mut rng, &utilities, CandidateID(1), 0.05);
+            assert_eq!(cloned.len(), 4);
+            assert_eq!(cloned[..3], utilities[..]);
+            assert!((cloned[3] - utilities[1]).abs() <= 0.05);
+            assert!((0f64..=1f64).contains(&cloned[3]));
+        }
+    }
+
+    #[test]
+    fn test_utility_matrices() {
+        let mut rng = StdRng::seed_from_u64(673);
+        let uniform = uniform_utility_matrix(&mut rng, 20, 4);
+        let beta = beta_utility_matrix(Beta::new(0.5, 0.5).unwrap(), &mut rng, 20, 4);
+        for matrix in [uniform, beta] {
+            assert_eq!(matrix.len(), 20);
+            assert!(matrix.iter().all(|row| row.len() == 4));
+            assert!(matrix.iter().flatten().all(|&u| (0f64..=1f64).contains(&u)));
+        }
+    }
+}